@@ -0,0 +1,96 @@
+use parity_codec::Codec;
+use runtime_primitives::traits::{As, CheckedAdd, CheckedSub, MaybeSerializeDebug, Member, SimpleArithmetic};
+use support::{decl_event, decl_module, decl_storage, dispatch::Result, ensure, Parameter, StorageMap, StorageValue};
+use system::ensure_signed;
+
+/// The identifier of an asset.
+pub type AssetId = u32;
+
+pub trait Trait: system::Trait {
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+	/// The type for recording the balance of an account in an asset.
+	type Balance: Parameter + Member + SimpleArithmetic + Codec + Default + Copy + As<usize> + As<u64> + MaybeSerializeDebug;
+}
+
+decl_event!(
+	pub enum Event<T>
+	where
+		<T as system::Trait>::AccountId,
+		<T as Trait>::Balance
+	{
+		// asset id, issuer, total supply
+		Issued(AssetId, AccountId, Balance),
+		// asset id, from, to, amount
+		Transferred(AssetId, AccountId, AccountId, Balance),
+	}
+);
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Assets {
+		// The balance of an account in an asset
+		Balances get(balance_of): map (AssetId, T::AccountId) => T::Balance;
+		// The total amount of an asset which was issued
+		TotalSupply get(total_supply): map AssetId => T::Balance;
+		// The id of the next asset to be issued
+		NextAssetId get(next_asset_id): AssetId;
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event<T>() = default;
+
+		/// issue a new asset, its whole supply goes to the sender
+		fn issue(origin, total: T::Balance) -> Result {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(total > T::Balance::sa(0), "The total supply has to be greater than zero");
+
+			let asset_id = Self::next_asset_id();
+			let next_asset_id = asset_id.checked_add(1).ok_or("Overflow adding a new asset")?;
+
+			<NextAssetId<T>>::put(next_asset_id);
+			<Balances<T>>::insert((asset_id, sender.clone()), total);
+			<TotalSupply<T>>::insert(asset_id, total);
+
+			Self::deposit_event(RawEvent::Issued(asset_id, sender, total));
+			Ok(())
+		}
+
+		/// transfer an amount of an asset to another account
+		fn transfer(origin, asset_id: AssetId, target: T::AccountId, amount: T::Balance) -> Result {
+			let sender = ensure_signed(origin)?;
+
+			Self::make_transfer(asset_id, &sender, &target, amount)
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Whether an asset has been issued.
+	pub fn exists(asset_id: AssetId) -> bool {
+		asset_id < Self::next_asset_id()
+	}
+
+	/// Move an amount of an asset from one account to another.
+	pub fn make_transfer(asset_id: AssetId, from: &T::AccountId, to: &T::AccountId, amount: T::Balance) -> Result {
+		ensure!(Self::exists(asset_id), "The asset does not exist");
+
+		let from_balance = Self::balance_of((asset_id, from.clone()));
+		let new_from_balance = from_balance
+			.checked_sub(&amount)
+			.ok_or("You don't have enough balance of the asset")?;
+
+		if from != to {
+			let new_to_balance = Self::balance_of((asset_id, to.clone()))
+				.checked_add(&amount)
+				.ok_or("Overflow adding to the balance of the asset")?;
+			<Balances<T>>::insert((asset_id, from.clone()), new_from_balance);
+			<Balances<T>>::insert((asset_id, to.clone()), new_to_balance);
+		}
+
+		Self::deposit_event(RawEvent::Transferred(asset_id, from.clone(), to.clone(), amount));
+		Ok(())
+	}
+}
@@ -1,6 +1,7 @@
 use parity_codec::{Decode, Encode};
 use rstd::prelude::*;
-use runtime_primitives::traits::{As, Hash};
+use runtime_primitives::traits::{As, CheckedMul, CheckedSub, Hash};
+use runtime_primitives::Perbill;
 use support::{
 	decl_event, decl_module, decl_storage,
 	dispatch::Result,
@@ -8,7 +9,53 @@ use support::{
 	traits::{Currency, ReservableCurrency},
 	StorageMap, StorageValue,
 };
-use system::ensure_signed;
+use system::{ensure_root, ensure_signed};
+
+use crate::assets::{self, AssetId};
+use crate::shares;
+
+/// The lifecycle state of a campaign.
+///
+/// The variants are encoded as a single byte in declaration order, so new
+/// variants must only ever be appended.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum CampaignStatus {
+	/// Still raising money
+	Funding,
+	/// Reached its target at the expiry
+	Succeeded,
+	/// Missed its target at the expiry, every investor can claim back the investment
+	Failed,
+	/// Cancelled by its manager, every investor can claim back the investment
+	Cancelled,
+	/// Succeeded, but a milestone was missed or rejected so the investors can claim back the escrowed money
+	Refunded,
+	/// Reached its target, the money waits in the escrow account for the manager to claim it
+	PayoutPending,
+}
+
+impl Default for CampaignStatus {
+	fn default() -> Self {
+		CampaignStatus::Funding
+	}
+}
+
+/// How a campaign which misses its target is settled at its expiry.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum FundingMode {
+	/// Every investor can claim back the investment when the target is missed
+	AllOrNothing,
+	/// The manager gets whatever was raised, minus the platform fee, even below the target
+	KeepItAll,
+}
+
+impl Default for FundingMode {
+	fn default() -> Self {
+		FundingMode::AllOrNothing
+	}
+}
 
 #[derive(Encode, Decode, Default, Clone, PartialEq)]
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -18,39 +65,243 @@ pub struct Campaign<Hash, AccountId, Balance, BlockNumber> {
 	campaign_name: Vec<u8>,
 	campaign_target_money: Balance,
 	campaign_expiry: BlockNumber,
-	// status 
-	// 0- Still raising money 
-	// 1- Succeeded 
-	// 2- Failed
-	campaign_status: u64,
+	campaign_status: CampaignStatus,
 }
 
-pub trait Trait: balances::Trait {
-	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+/// A stage of a campaign with milestones, a share of the raised money is released when it is reached.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Milestone<BlockNumber> {
+	pub description: Vec<u8>,
+	// The percentage of the raised money released by this milestone
+	pub percentage: u32,
+	// The milestone has to be submitted before this block
+	pub deadline: BlockNumber,
+}
+
+/// A reward an investor can claim by pledging at least `min_pledge` to a campaign.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct RewardTier<Balance> {
+	pub min_pledge: Balance,
+	pub title: Vec<u8>,
+	// The number of investors who can claim the tier, unlimited if `None`
+	pub quantity: Option<u32>,
+}
+
+/// How the raised money of a succeeded campaign is released to its manager, counted from its finalization.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct VestingSchedule<BlockNumber> {
+	// Nothing can be claimed before this many blocks
+	pub cliff: BlockNumber,
+	// The money is released linearly over this many blocks
+	pub duration: BlockNumber,
+}
+
+/// A quadratic funding round, its matching pool is shared by its campaigns when it ends.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct FundingRound<Balance, BlockNumber> {
+	pub matching_pool: Balance,
+	pub end: BlockNumber,
 }
 
-const MAX_CAMPAIGNS_PER_BLOCK: usize = 3;
+/// An offer of a sponsor to match the investments in a campaign, its cap is reserved from the sponsor.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct SponsorOffer<Balance> {
+	// The matched money per 100 of invested money, 100 matches one to one
+	pub match_percentage: u32,
+	pub cap: Balance,
+	// The matched money which was drawn into the campaign so far
+	pub drawn: Balance,
+}
+
+/// Optional details to render the page of a campaign, stored apart from the campaign itself.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct CampaignMetadata<Hash> {
+	// The hash of the full description, which is kept off chain
+	pub description_hash: Option<Hash>,
+	pub url: Option<Vec<u8>>,
+	pub category: Option<Vec<u8>>,
+	// The content hash of the image of the campaign
+	pub image_hash: Option<Hash>,
+}
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
+
+pub trait Trait: assets::Trait + shares::Trait {
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+	/// The currency of the deposits and of the campaigns which don't raise money in an asset,
+	/// its balance is the balance of the assets as well.
+	type Currency: ReservableCurrency<Self::AccountId, Balance = <Self as assets::Trait>::Balance>;
+
+	/// The maximum length of a campaign name.
+	const MAX_NAME_LENGTH: usize;
+	/// The maximum length of the url and the category in the metadata of a campaign.
+	const MAX_METADATA_FIELD_LENGTH: usize;
+	/// The maximum number of investors of a campaign.
+	const MAX_INVESTORS_PER_CAMPAIGN: u64;
+	/// The maximum number of milestones of a campaign.
+	const MAX_MILESTONES_PER_CAMPAIGN: usize;
+	/// The maximum number of reward tiers of a campaign.
+	const MAX_REWARD_TIERS_PER_CAMPAIGN: usize;
+	/// The maximum number of stretch goals of a campaign.
+	const MAX_STRETCH_GOALS_PER_CAMPAIGN: usize;
+	/// The maximum number of co-managers of a campaign.
+	const MAX_CO_MANAGERS_PER_CAMPAIGN: usize;
+	/// The maximum number of campaigns taking part in a funding round.
+	const MAX_CAMPAIGNS_PER_ROUND: usize;
+	/// The maximum number of sponsors matching the investments in a campaign.
+	const MAX_SPONSORS_PER_CAMPAIGN: usize;
+	/// The blocks a submitted milestone waits before its money can be released.
+	const MILESTONE_REVIEW_PERIOD: Self::BlockNumber;
+	/// The percentage of the raised money whose investors have to reject a milestone to get refunded.
+	const MILESTONE_REJECTION_THRESHOLD: u64;
+	/// The maximum number of investors refunded by the settlement queue in a block.
+	const SETTLEMENTS_PER_BLOCK: u64;
+	/// The blocks a settled campaign is kept after its last status change before it can be reaped.
+	const REAP_GRACE_PERIOD: Self::BlockNumber;
+	/// The maximum number of investors whose storage is removed by a call reaping a campaign.
+	const INVESTORS_REAPED_PER_CALL: u64;
+
+	/// The account holding the invested money of every campaign until it is paid out or refunded.
+	/// Nobody should know the private key of this account.
+	fn escrow_account() -> Self::AccountId;
+}
 
 decl_event!(
 	pub enum Event<T>
 	where
+		Balance = BalanceOf<T>,
 		<T as system::Trait>::AccountId,
 		<T as system::Trait>::Hash,
-		<T as balances::Trait>::Balance,
 		<T as system::Trait>::BlockNumber
 	{
-		CreateCampaign(AccountId, Hash, Balance, Balance, BlockNumber),
+		CreateCampaign(AccountId, Hash, Balance, Balance, BlockNumber, FundingMode),
 		Invest(Hash, AccountId, Balance),
-		CampaignFinalized(Hash, Balance, BlockNumber, bool),
+		// campaign id, raised money, platform fee, block number, status
+		CampaignFinalized(Hash, Balance, Balance, BlockNumber, CampaignStatus),
+		CampaignPeriodLimitChanged(BlockNumber),
+		MinimumTargetChanged(Balance),
+		MinimumInvestmentChanged(Balance),
+		PlatformFeeChanged(Perbill),
+		TreasuryAccountChanged(AccountId),
+		CampaignCancelled(Hash, Balance, BlockNumber),
+		FundsClaimed(Hash, AccountId, Balance),
+		RefundClaimed(Hash, AccountId, Balance),
+		// campaign id, investors processed so far, number of investors
+		SettlementProgress(Hash, u64, u64),
+		SettlementCompleted(Hash),
+		StorageDepositChanged(Balance),
+		CreationDepositChanged(Balance),
+		CreationDepositHoldChanged(BlockNumber),
+		// campaign id, the creation deposit returned once the hold of the cancelled campaign is over
+		CreationDepositReleased(Hash, Balance),
+		// campaign id, the slashed creation deposit
+		CampaignSlashed(Hash, Balance),
+		// the existential deposit the treasury failed to give the escrow account
+		EscrowAccountUnendowed(Balance),
+		// number of migrated campaigns
+		MigrationCompleted(u64),
+		// campaign id, the account which reaped it
+		CampaignReaped(Hash, AccountId),
+		// campaign id, investors reaped so far, number of investors
+		ReapProgress(Hash, u64, u64),
+		WithdrawInvestment(Hash, AccountId, Balance),
+		MetadataUpdated(Hash),
+		CampaignExtended(Hash, BlockNumber),
+		TargetUpdated(Hash, Balance),
+		// campaign id, current owner, proposed owner
+		CampaignTransferProposed(Hash, AccountId, AccountId),
+		// campaign id, previous owner, new owner
+		CampaignTransferred(Hash, AccountId, AccountId),
+		// campaign id, number of co-managers, approval threshold
+		ManagersChanged(Hash, u32, u32),
+		// campaign id, action hash, approving manager, approvals so far
+		ManagerActionApproved(Hash, Hash, AccountId, u32),
+		StretchGoalReached(Hash, u32, Balance),
+		RewardTierClaimed(Hash, AccountId, u32),
+		RewardTierReleased(Hash, AccountId, u32),
+		MilestoneSubmitted(Hash, u32, BlockNumber),
+		MilestoneReleased(Hash, u32, Balance),
+		MilestoneVoted(Hash, u32, AccountId, bool, Balance),
+		MilestoneRejected(Hash, u32),
+		EscrowRefunded(Hash, Balance),
+		// round id, end of the round
+		RoundCreated(u64, BlockNumber),
+		RoundFunded(u64, AccountId, Balance),
+		RoundJoined(Hash, u64),
+		// campaign id, round id, matched money
+		RoundMatched(Hash, u64, Balance),
+		// round id, distributed money, money returned to the treasury
+		RoundClosed(u64, Balance, Balance),
+		// campaign id, sponsor, match percentage, cap
+		MatchOffered(Hash, AccountId, u32, Balance),
+		MatchDrawn(Hash, AccountId, Balance),
+		// campaign id, sponsor, match given back because the investor withdrew
+		MatchReturned(Hash, AccountId, Balance),
+		// campaign id, sponsor, unused cap
+		MatchReleased(Hash, AccountId, Balance),
 	}
 );
 
 decl_storage! {
 	trait Store for Module<T: Trait> as KickstartModule {
-		Campaigns get(campaign): map T::Hash => Campaign<T::Hash, T::AccountId, T::Balance, T::BlockNumber>;
+		Campaigns get(campaign): map T::Hash => Campaign<T::Hash, T::AccountId, BalanceOf<T>, T::BlockNumber>;
 		CampaignOwner get(owner_of_campaign): map T::Hash => Option<T::AccountId>;
+		// The account a campaign is being handed over to, until it accepts the campaign
+		PendingCampaignOwner get(pending_owner_of_campaign): map T::Hash => Option<T::AccountId>;
+		// The accounts managing a campaign together with its manager
+		CoManagers get(co_managers_of): map T::Hash => Vec<T::AccountId>;
+		// The number of managers who have to approve a privileged action on a campaign, one if unset
+		ManagerThreshold get(manager_threshold_of): map T::Hash => u32;
+		// The managers who approved a pending action on a campaign, keyed by the hash of the action
+		ManagerApprovals get(manager_approvals_of): map (T::Hash, T::Hash) => Vec<T::AccountId>;
+		// The actions of a campaign which are waiting for more approvals
+		PendingManagerActions get(pending_manager_actions_of): map T::Hash => Vec<T::Hash>;
 		// to be added in the genesis configuration
 		CampaignPeriodLimit get(campaign_period_limit) config(): T::BlockNumber = T::BlockNumber::sa(864000);
+		// The lowest target money of a campaign
+		MinimumTarget get(minimum_target) config(): BalanceOf<T>;
+		// The lowest amount of money for a single investment
+		MinimumInvestment get(minimum_investment) config(): BalanceOf<T>;
+		// The share of the money of a succeeded campaign which is paid to the treasury account
+		PlatformFee get(platform_fee) config(): Perbill;
+		// The account receiving the platform fee
+		TreasuryAccount get(treasury_account) config(): T::AccountId;
+		// The money reserved from the manager for the storage of a campaign, returned when it is reaped
+		StorageDeposit get(storage_deposit) config(): BalanceOf<T>;
+		// The storage deposit reserved for a campaign
+		CampaignDeposit get(campaign_deposit): map T::Hash => BalanceOf<T>;
+		// The money reserved from the manager when creating a campaign, returned when the campaign
+		// is finalized or the hold of the cancelled campaign is over, and slashed when it is flagged as spam or fraud
+		CreationDeposit get(creation_deposit) config(): BalanceOf<T>;
+		// The number of blocks the creation deposit of a cancelled campaign stays reserved, so that
+		// cancelling a campaign doesn't dodge a slash
+		CreationDepositHold get(creation_deposit_hold) config(): T::BlockNumber;
+		// The creation deposit which is still reserved for a campaign
+		CreationDepositOf get(creation_deposit_of): map T::Hash => BalanceOf<T>;
+		// The platform fee charged on a succeeded campaign
+		PlatformFeeOf get(platform_fee_of): map T::Hash => BalanceOf<T>;
+		// The escrowed money of a campaign which its investors claim back pro rata to their investments
+		RefundPool get(refund_pool): map T::Hash => BalanceOf<T>;
+		// Whether an investor has claimed back his/her share of the refund pool
+		Settled get(is_settled): map (T::Hash, T::AccountId) => bool;
+		// The number of investors of a campaign who have been refunded
+		SettledCount get(settled_count): map T::Hash => u64;
+		// Whether an investor of a succeeded campaign has been issued his/her backer shares
+		SharesIssuedTo get(has_shares): map (T::Hash, T::AccountId) => bool;
+		// The campaigns whose investors are refunded, or issued backer shares when the campaign
+		// succeeded, a few at a time at the start of every block
+		SettlementQueue get(settlement_queue): Vec<T::Hash>;
+		// The index into `InvestAccounts` of the next investor of a queued campaign to refund
+		SettlementCursor get(settlement_cursor): map T::Hash => u64;
+		// The index into `InvestAccounts` of the next investor of a campaign being reaped
+		ReapCursor get(reap_cursor): map T::Hash => u64;
 
 		CampaignsByBlockNumber get(campaign_expire_at): map T::BlockNumber => Vec<T::Hash>;
 		
@@ -66,16 +317,89 @@ decl_storage! {
 		InvestedCampaignsCount get(invested_campaign_count): map T::AccountId => u64;
 		InvestedCampaignsIndex: map (T::AccountId, T::Hash) => u64;
 
-		InvestAmount get(invest_amount_of): map (T::Hash, T::AccountId) => T::Balance;
+		InvestAmount get(invest_amount_of): map (T::Hash, T::AccountId) => BalanceOf<T>;
 		InvestAccounts get(invest_accounts): map T::Hash => Vec<T::AccountId>;
 		InvestAccountsCount get(invest_accounts_count): map T::Hash => u64;
 
 		// The total amount of money the Campaign has got
-		CampaignSupportedAmount get(total_amount_of_campaign): map T::Hash => T::Balance;
+		CampaignSupportedAmount get(total_amount_of_campaign): map T::Hash => BalanceOf<T>;
+		// The money of a campaign which is still held in the escrow account
+		EscrowBalance get(escrow_balance): map T::Hash => BalanceOf<T>;
+
+		StatusOfCampaign get(campaign_status): map T::Hash => CampaignStatus;
+		// The block at which the status of a campaign last changed
+		StatusChangedAt get(status_changed_at): map T::Hash => T::BlockNumber;
+
+		// Whether a campaign keeps the money raised below its target
+		FundingModeOf get(funding_mode_of): map T::Hash => FundingMode;
+		// The asset a campaign raises money in, the native currency if unset
+		CampaignAsset get(asset_of_campaign): map T::Hash => Option<AssetId>;
+		// The schedule releasing the raised money of a campaign to its manager, all at once if unset
+		VestingSchedules get(vesting_schedule_of): map T::Hash => Option<VestingSchedule<T::BlockNumber>>;
+		// The money of a succeeded campaign which vests, it starts vesting when the campaign is finalized
+		VestingTotal get(vesting_total_of): map T::Hash => BalanceOf<T>;
+		// Whether the managers of a campaign may finalize it as soon as it reaches its target
+		EarlyCloseAllowed get(early_close_allowed): map T::Hash => bool;
+
+		// The details of a campaign which are only needed to display it
+		Metadata get(metadata_of): map T::Hash => CampaignMetadata<T::Hash>;
+
+		// The ascending stretch goals of a campaign above its target
+		StretchGoals get(stretch_goals_of): map T::Hash => Vec<BalanceOf<T>>;
+		// The number of stretch goals the campaign has reached, final once the campaign is finalized
+		StretchGoalsReached get(stretch_goals_reached): map T::Hash => u32;
 
-		CampaignStatus get(campaign_status): map T::Hash => u64;
+		// The reward tiers of a campaign
+		RewardTiers get(reward_tiers_of): map T::Hash => Vec<RewardTier<BalanceOf<T>>>;
+		// The number of investors who claimed a reward tier of a campaign
+		RewardTierClaimCount get(reward_tier_claim_count): map (T::Hash, u32) => u32;
+		// The reward tier an investor claimed on a campaign
+		ClaimedRewardTier get(claimed_reward_tier_of): map (T::Hash, T::AccountId) => Option<u32>;
+
+		// The milestones of a campaign, its raised money is held in escrow and released milestone by milestone
+		Milestones get(milestones_of): map T::Hash => Vec<Milestone<T::BlockNumber>>;
+		// The index of the next milestone to be submitted
+		NextMilestone get(next_milestone): map T::Hash => u32;
+		// The index of the submitted milestone and the block it was submitted at
+		MilestoneSubmission get(milestone_submission): map T::Hash => Option<(u32, T::BlockNumber)>;
+		// The vote of an investor on a milestone, true approves it
+		MilestoneVotes get(milestone_vote_of): map (T::Hash, u32, T::AccountId) => Option<bool>;
+		// The invested money approving and rejecting a milestone
+		MilestoneTally get(milestone_tally): map (T::Hash, u32) => (BalanceOf<T>, BalanceOf<T>);
+
+		// The number of funding rounds ever created, the id of the next round
+		RoundCount get(round_count): u64;
+		// The funding rounds which have not ended yet
+		Rounds get(round): map u64 => Option<FundingRound<BalanceOf<T>, T::BlockNumber>>;
+		// The campaigns taking part in a funding round
+		RoundCampaigns get(campaigns_of_round): map u64 => Vec<T::Hash>;
+		// The funding round a campaign takes part in
+		RoundOfCampaign get(round_of_campaign): map T::Hash => Option<u64>;
+		RoundsByBlockNumber get(rounds_end_at): map T::BlockNumber => Vec<u64>;
+		// The money a campaign got from the matching pools of funding rounds. Refunds only pay back
+		// the share of the investors, so the match of a failed campaign goes to the treasury when it is reaped
+		MatchedAmount get(matched_amount_of): map T::Hash => BalanceOf<T>;
+		// The contribution of an investor which counts towards the quadratic match of a campaign
+		CountedContribution get(counted_contribution_of): map (T::Hash, T::AccountId) => BalanceOf<T>;
+		// The sum of the square roots and the sum of the counted contributions of a campaign,
+		// kept up to date so that closing a round doesn't go through the investors
+		ContributionSums get(contribution_sums_of): map T::Hash => (BalanceOf<T>, BalanceOf<T>);
+
+		// The offers of sponsors to match the investments in a campaign until it stops raising money
+		SponsorOffers get(sponsor_offer_of): map (T::Hash, T::AccountId) => Option<SponsorOffer<BalanceOf<T>>>;
+		CampaignSponsors get(sponsors_of): map T::Hash => Vec<T::AccountId>;
+		// The matches the investments of an investor drew from each sponsor of a campaign
+		MatchesDrawnBy get(matches_drawn_by): map (T::Hash, T::AccountId) => Vec<(T::AccountId, BalanceOf<T>)>;
 
 		Nonce: u64;
+
+		// Whether the campaigns created before the escrow rework have been migrated, their status from a `u64`
+		// to `CampaignStatus` and their pledges reserved on the investors into the escrow account
+		CampaignsMigrated get(campaigns_migrated): bool;
+		// The number of campaigns created before the escrow rework, set once the escrow account is endowed
+		MigrationEnd get(migration_end): Option<u64>;
+		// The index of the next campaign to migrate and of its next investor
+		MigrationCursor get(migration_cursor): (u64, u64);
 	}
 }
 
@@ -83,16 +407,20 @@ decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 		fn deposit_event<T>() = default;
 
-		fn create_funding(origin, project_name: Vec<u8>, target_money: T::Balance, support_money: T::Balance, expiry: T::BlockNumber) -> Result {
+		fn create_funding(origin, project_name: Vec<u8>, target_money: BalanceOf<T>, support_money: BalanceOf<T>, expiry: T::BlockNumber, milestones: Vec<Milestone<T::BlockNumber>>, reward_tiers: Vec<RewardTier<BalanceOf<T>>>, stretch_goals: Vec<BalanceOf<T>>, funding_mode: FundingMode, allow_early_close: bool, asset_id: Option<AssetId>, vesting: Option<VestingSchedule<T::BlockNumber>>) -> Result {
 			let sender = ensure_signed(origin)?;
 			
 			let nonce = <Nonce<T>>::get();
 			let campaign_id = (<system::Module<T>>::random_seed(), &sender, nonce)
 				.using_encoded(<T as system::Trait>::Hashing::hash);
 			ensure!(!<CampaignOwner<T>>::exists(&campaign_id), "Campaign already exists");
+			// The campaigns created before the escrow rework are known once the escrow account is endowed
+			ensure!(Self::campaigns_migrated() || Self::migration_end().is_some(), "The escrow account is not endowed yet");
 			
 			// ensure support_money <= target_money
 			ensure!(support_money <= target_money, "You already have enough money");
+			ensure!(target_money >= Self::minimum_target(), "The target money is lower than the minimum target");
+			ensure!(project_name.len() <= T::MAX_NAME_LENGTH, "The project name is too long");
 			
 			let new_campaign = Campaign{
 				campaign_id: campaign_id.clone(),
@@ -100,30 +428,111 @@ decl_module! {
 				campaign_name: project_name,
 				campaign_target_money: target_money,
 				campaign_expiry: expiry,
-				campaign_status: 0,				//Still raising money
+				campaign_status: CampaignStatus::Funding,
 			};
 
 			// ensuring validation of the expiry
 			ensure!(expiry > <system::Module<T>>::block_number(), "The expiry has to be greater than the current block number");
 			ensure!(expiry <= <system::Module<T>>::block_number() + Self::campaign_period_limit(), "The expiry has be lower than the limit block number");
 
-			// ensuring maximum number of campaign in a block
-			let campaigns = Self::campaign_expire_at(expiry);
-			ensure!(campaigns.len() < MAX_CAMPAIGNS_PER_BLOCK, "Maximum number of campaigns is reached for the target block, move to next block");
+			Self::ensure_valid_milestones(&milestones, expiry)?;
+			Self::ensure_valid_reward_tiers(&reward_tiers)?;
+			Self::ensure_valid_stretch_goals(&stretch_goals, target_money)?;
+			if let Some(asset_id) = asset_id {
+				ensure!(<assets::Module<T>>::exists(asset_id), "The asset does not exist");
+			}
+			if let Some(ref schedule) = vesting {
+				ensure!(milestones.is_empty(), "A campaign with milestones can't have a vesting schedule");
+				ensure!(schedule.duration > T::BlockNumber::sa(0), "The duration of the vesting has to be greater than zero");
+				ensure!(schedule.cliff <= schedule.duration, "The cliff of the vesting can't be longer than its duration");
+			}
+
+			// Verify first Execute Last: everything minting the campaign checks is checked before anything is reserved
+			ensure!(Self::campaign_count().checked_add(1).is_some(), "Overflow adding a new Campaign");
+			ensure!(Self::owned_campaign_count(&sender).checked_add(1).is_some(), "Overflow adding a new Campaign");
+			if support_money > BalanceOf::<T>::sa(0) {
+				ensure!(
+					Self::invested_campaign_count(&sender).checked_add(1).is_some(),
+					"Overflow adding a new invested Campaign"
+				);
+			}
+
+			// the storage deposit is returned when the campaign is reaped,
+			// and the creation deposit when the campaign is finalized
+			let deposit = Self::storage_deposit();
+			let creation_deposit = Self::creation_deposit();
+			match asset_id {
+				Some(asset_id) => {
+					ensure!(
+						T::Currency::free_balance(&sender) >= deposit + creation_deposit,
+						"You don't have enough free balance for the deposits of the campaign"
+					);
+					ensure!(
+						<assets::Module<T>>::balance_of((asset_id, sender.clone())) >= support_money,
+						"You don't have enough balance of the asset for the support money"
+					);
+				},
+				None => ensure!(
+					T::Currency::free_balance(&sender) >= deposit + creation_deposit + support_money,
+					"You don't have enough free balance for the deposits and the support money of the campaign"
+				),
+			}
+
+			T::Currency::reserve(&sender, deposit + creation_deposit)?;
+			// The support money is invested when minting, so the asset of the campaign has to be known first
+			if let Some(asset_id) = asset_id {
+				<CampaignAsset<T>>::insert(&campaign_id, asset_id);
+			}
+			if let Err(e) = Self::mint(sender.clone(), campaign_id.clone(), expiry.clone(), support_money.clone(), new_campaign) {
+				let _ = T::Currency::unreserve(&sender, deposit + creation_deposit);
+				return Err(e);
+			}
+			<CampaignDeposit<T>>::insert(&campaign_id, deposit);
+			<CreationDepositOf<T>>::insert(&campaign_id, creation_deposit);
 
-			Self::mint(sender.clone(), campaign_id.clone(), expiry.clone(), support_money.clone(), new_campaign)?;
+			if !milestones.is_empty() {
+				<Milestones<T>>::insert(&campaign_id, milestones);
+			}
+			if !reward_tiers.is_empty() {
+				<RewardTiers<T>>::insert(&campaign_id, reward_tiers);
+			}
+			if !stretch_goals.is_empty() {
+				<StretchGoals<T>>::insert(&campaign_id, stretch_goals);
+			}
+			if funding_mode != FundingMode::default() {
+				<FundingModeOf<T>>::insert(&campaign_id, funding_mode);
+			}
+			if allow_early_close {
+				<EarlyCloseAllowed<T>>::insert(&campaign_id, true);
+			}
+			if let Some(schedule) = vesting {
+				<VestingSchedules<T>>::insert(&campaign_id, schedule);
+			}
 
 			// deposit the event
-			Self::deposit_event(RawEvent::CreateCampaign(sender, campaign_id, target_money, support_money, expiry));
+			Self::deposit_event(RawEvent::CreateCampaign(sender, campaign_id, target_money, support_money, expiry, funding_mode));
 			Ok(())
 		}
 
-		/// invest a project
-		fn invest(origin, campaign_id: T::Hash, invest_amount: T::Balance) -> Result {
+		/// invest a project, optionally claiming one of its reward tiers
+		fn invest(origin, campaign_id: T::Hash, invest_amount: BalanceOf<T>, reward_tier: Option<u32>) -> Result {
 			let sender = ensure_signed(origin)?;
 
-			let owner = Self::owner_of_campaign(campaign_id).ok_or("Campaign has no owner")?;
-			ensure!(owner != sender, "You can't invest for your own project");
+			ensure!(<CampaignOwner<T>>::exists(campaign_id), "Campaign has no owner");
+			Self::ensure_migrated(campaign_id)?;
+			ensure!(!Self::is_manager(campaign_id, &sender), "You can't invest for your own project");
+			// The investment of a sponsor is made of its matches only, which it can't withdraw
+			ensure!(
+				!<SponsorOffers<T>>::exists((campaign_id, sender.clone())),
+				"A sponsor can't invest in the campaign it sponsors"
+			);
+			ensure!(invest_amount >= Self::minimum_investment(), "The invest amount is lower than the minimum investment");
+
+			// Verify first, the reward tier has to be available for the whole pledge after investing
+			if let Some(tier_index) = reward_tier {
+				let pledge = Self::invest_amount_of((campaign_id, sender.clone())) + invest_amount;
+				Self::ensure_reward_tier_available(campaign_id, &sender, tier_index, pledge)?;
+			}
 
 			// The investor had not invested the project before
 			if !<InvestAmount<T>>::exists((campaign_id.clone(), sender.clone())){
@@ -131,209 +540,1935 @@ decl_module! {
 			}else{
 				Self::invest_before(sender.clone(), campaign_id.clone(), invest_amount.clone())?;
 			}
+			Self::recount_contribution(campaign_id, &sender);
+
+			Self::deposit_event(RawEvent::Invest(campaign_id, sender.clone(), invest_amount));
 
-			Self::deposit_event(RawEvent::Invest(campaign_id, sender, invest_amount));
+			Self::draw_matches(campaign_id, &sender, invest_amount);
+			Self::update_stretch_goals(campaign_id);
+
+			if let Some(tier_index) = reward_tier {
+				Self::set_reward_tier(campaign_id, sender, Some(tier_index));
+			}
 
 			Ok(())
 		}
 
-		fn on_finalize() {
-		// get all the Campaign present in the block
-			let block_number = <system::Module<T>>::block_number();
-			let campaign_hash = Self::campaign_expire_at(block_number);
+		/// withdraw part or all of an investment from a campaign which is still raising money
+		fn withdraw_investment(origin, campaign_id: T::Hash, withdraw_amount: BalanceOf<T>) -> Result {
+			let sender = ensure_signed(origin)?;
 
-			for campaign_id in &campaign_hash{
-				let mut campaign = Self::campaign(campaign_id);
-				let amount_of_investment = Self::total_amount_of_campaign(campaign_id);
-				if amount_of_investment >= campaign.campaign_target_money{
-					// Make the status success
-					campaign.campaign_status = 1;
-					<Campaigns<T>>::insert(campaign_id.clone(), campaign);
-					// Get the owner of the funding
-					let _owner = Self::owner_of_campaign(campaign_id);
-					match _owner {
-						Some(owner) => {
-							// Get all the investors
-							let investors = Self::invest_accounts(campaign_id);
-							let mut no_error = true;
-							// Iterate every investor, unreserve the money that he/she had invested and transfer it to owner
-							'inner: for investor in &investors{
-								let invest_balance = Self::invest_amount_of((*campaign_id, investor.clone()));
-								let _ = <balances::Module<T>>::unreserve(&investor, invest_balance.clone());
-								// If the investor is owner, just unreserve the money
-								if investor == &owner{ continue;}
-								let _currency_transfer = <balances::Module<T> as Currency<_>>::transfer(&investor, &owner, invest_balance);
-								match _currency_transfer {
-									Err(_e) => {
-										no_error = false;
-										break 'inner;
-									},
-									Ok(_v) => {}
-								}
-							}
-							if no_error {
-								let _ = <balances::Module<T>>::reserve(&owner, amount_of_investment);
-								// deposit the event
-								Self::deposit_event(RawEvent::CampaignFinalized(*campaign_id, amount_of_investment, block_number, true));
-							}
-						},
-						None => continue,
-					}
-				}else{ // refund all of the money
-					// Make the status fail
-					campaign.campaign_status = 2;
-					<Campaigns<T>>::insert(campaign_id.clone(), campaign);
-					let campaign_accounts = Self::invest_accounts(campaign_id);
-					for account in campaign_accounts {
-						let invest_balance = Self::invest_amount_of((*campaign_id, account.clone()));
-						let _ = <balances::Module<T>>::unreserve(&account, invest_balance);
-					}
-					// deposit the event
-					Self::deposit_event(RawEvent::CampaignFinalized(*campaign_id, amount_of_investment, block_number, false));
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			Self::ensure_migrated(campaign_id)?;
+			ensure!(<InvestAmount<T>>::exists((campaign_id.clone(), sender.clone())), "You have not invested in this campaign");
+			ensure!(withdraw_amount > BalanceOf::<T>::sa(0), "The withdraw amount has to be greater than zero");
+			ensure!(
+				!<SponsorOffers<T>>::exists((campaign_id, sender.clone())),
+				"A sponsor can't withdraw from the campaign"
+			);
+
+			let campaign = Self::campaign(&campaign_id);
+			ensure!(campaign.campaign_status == CampaignStatus::Funding, "This campaign is not raising money anymore.");
+			ensure!(<system::Module<T>>::block_number() < campaign.campaign_expiry, "This campaign is expired.");
+
+			let amount_of_investor_on_campaign = Self::invest_amount_of((campaign_id.clone(), sender.clone()));
+			ensure!(withdraw_amount <= amount_of_investor_on_campaign, "You can't withdraw more than you have invested");
+			let new_amount_of_investor_on_campaign = amount_of_investor_on_campaign - withdraw_amount;
+
+			// Verify first, everything updated after the money is given back has to be checked before
+			let new_amount_of_campaign = Self::total_amount_of_campaign(&campaign_id)
+				.checked_sub(&withdraw_amount)
+				.ok_or("Underflow subtracting the withdraw amount from the campaign")?;
+			let new_escrow_balance = Self::escrow_balance(&campaign_id)
+				.checked_sub(&withdraw_amount)
+				.ok_or("Underflow subtracting the withdraw amount from the escrowed money of the campaign")?;
+			if new_amount_of_investor_on_campaign == BalanceOf::<T>::sa(0) {
+				ensure!(
+					Self::invest_accounts_count(&campaign_id).checked_sub(1).is_some(),
+					"Underflow subtracting the total number of investors of a campaign"
+				);
+				ensure!(
+					Self::invested_campaign_count(&sender).checked_sub(1).is_some(),
+					"Underflow removing an invested Campaign"
+				);
+			}
+
+			// give the money back from the escrow account
+			Self::transfer_pledge(campaign_id, &T::escrow_account(), &sender, withdraw_amount)?;
+			<EscrowBalance<T>>::insert(&campaign_id, new_escrow_balance);
+
+			// The investor withdraws everything, so remove him/her from the investors
+			if new_amount_of_investor_on_campaign == BalanceOf::<T>::sa(0) {
+				Self::remove_investor(sender.clone(), campaign_id.clone())?;
+			} else {
+				<InvestAmount<T>>::insert(
+					(campaign_id.clone(), sender.clone()),
+					new_amount_of_investor_on_campaign,
+				);
+			}
+			Self::recount_contribution(campaign_id, &sender);
+
+			// The remaining pledge may not be enough for the claimed reward tier anymore
+			if let Some(tier_index) = Self::claimed_reward_tier_of((campaign_id, sender.clone())) {
+				let tiers = Self::reward_tiers_of(&campaign_id);
+				let keeps_tier = tiers.get(tier_index as usize)
+					.map_or(false, |tier| new_amount_of_investor_on_campaign >= tier.min_pledge);
+				if !keeps_tier {
+					Self::set_reward_tier(campaign_id, sender.clone(), None);
 				}
 			}
+
+			// subtract the withdraw amount from the total amount of the project
+			<CampaignSupportedAmount<T>>::insert(&campaign_id, new_amount_of_campaign);
+			Self::return_matches(campaign_id, &sender, withdraw_amount, amount_of_investor_on_campaign);
+			Self::update_stretch_goals(campaign_id);
+
+			Self::deposit_event(RawEvent::WithdrawInvestment(campaign_id, sender, withdraw_amount));
+			Ok(())
 		}
-	}
-}
 
-impl<T: Trait> Module<T> {
-	fn mint(
-		sender: T::AccountId,
-		campaign_id: T::Hash,
-		expiry: T::BlockNumber,
-		support_money: T::Balance,
-		new_campaign: Campaign<T::Hash, T::AccountId, T::Balance, T::BlockNumber>,
-	) -> Result {
-		// updating the global states
-		<Campaigns<T>>::insert(campaign_id.clone(), new_campaign.clone());
-		<CampaignOwner<T>>::insert(campaign_id.clone(), sender.clone());
+		/// match the investments in a campaign by `match_percentage` per 100 up to `cap`, which is reserved until the campaign stops raising money
+		fn offer_match(origin, campaign_id: T::Hash, match_percentage: u32, cap: BalanceOf<T>) -> Result {
+			let sender = ensure_signed(origin)?;
 
-		<CampaignsByBlockNumber<T>>::mutate(expiry, |campaigns| campaigns.push(campaign_id.clone()));
-		//Verify first Execute Last
-		let campaign_count = Self::campaign_count();
-		let new_campaign_count = campaign_count
-			.checked_add(1)
-			.ok_or("Overflow adding a new Campaign")?;
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			Self::ensure_migrated(campaign_id)?;
+			let campaign = Self::campaign(&campaign_id);
+			ensure!(campaign.campaign_status == CampaignStatus::Funding, "This campaign is not raising money anymore.");
+			ensure!(<system::Module<T>>::block_number() < campaign.campaign_expiry, "This campaign is expired.");
+			ensure!(!Self::is_manager(campaign_id, &sender), "You can't sponsor your own project");
+			ensure!(Self::asset_of_campaign(&campaign_id).is_none(), "Only a campaign in the native currency can be sponsored");
+			ensure!(match_percentage > 0, "The match percentage has to be greater than zero");
+			ensure!(cap > BalanceOf::<T>::sa(0), "The cap has to be greater than zero");
+			ensure!(!<SponsorOffers<T>>::exists((campaign_id, sender.clone())), "You already sponsor this campaign");
+			ensure!(
+				!<InvestAmount<T>>::exists((campaign_id, sender.clone())),
+				"An investor of the campaign can't sponsor it"
+			);
+			ensure!(
+				Self::sponsors_of(&campaign_id).len() < T::MAX_SPONSORS_PER_CAMPAIGN,
+				"Too many sponsors for a campaign"
+			);
 
-		<AllCampaignArray<T>>::insert(&campaign_count, campaign_id.clone());
-		<AllCampaignCount<T>>::put(new_campaign_count);
-		<AllCampaignIndex<T>>::insert(campaign_id.clone(), campaign_count);
+			T::Currency::reserve(&sender, cap)?;
+			<SponsorOffers<T>>::insert(
+				(campaign_id, sender.clone()),
+				SponsorOffer { match_percentage, cap, drawn: BalanceOf::<T>::sa(0) },
+			);
+			<CampaignSponsors<T>>::mutate(&campaign_id, |sponsors| sponsors.push(sender.clone()));
 
-		let owned_campaign_count = Self::owned_campaign_count(&sender);
-		let new_owned_campaign_count = owned_campaign_count
-			.checked_add(1)
-			.ok_or("Overflow adding a new Campaign")?;
+			Self::deposit_event(RawEvent::MatchOffered(campaign_id, sender, match_percentage, cap));
+			Ok(())
+		}
 
-		<OwnedCampaignArray<T>>::insert(
-			(sender.clone(), owned_campaign_count.clone()),
-			campaign_id.clone(),
-		);
-		<OwnedCampaignCount<T>>::insert(&sender, new_owned_campaign_count);
-		<OwnedCampaignIndex<T>>::insert((sender.clone(), campaign_id.clone()), owned_campaign_count);
+		/// update the metadata of a campaign which is still raising money
+		fn update_metadata(origin, campaign_id: T::Hash, metadata: CampaignMetadata<T::Hash>) -> Result {
+			let sender = ensure_signed(origin)?;
 
-		if support_money > T::Balance::sa(0) {
-			Self::not_invest_before(sender.clone(), campaign_id.clone(), support_money.clone())?;
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			let campaign = Self::campaign(&campaign_id);
+			ensure!(Self::is_manager(campaign_id, &sender), "Only the campaign managers can update the metadata");
+			ensure!(campaign.campaign_status == CampaignStatus::Funding, "This campaign is not raising money anymore.");
+			ensure!(<system::Module<T>>::block_number() < campaign.campaign_expiry, "This campaign is expired.");
+
+			let url_length = metadata.url.as_ref().map_or(0, |url| url.len());
+			ensure!(url_length <= T::MAX_METADATA_FIELD_LENGTH, "The url of the campaign is too long");
+			let category_length = metadata.category.as_ref().map_or(0, |category| category.len());
+			ensure!(category_length <= T::MAX_METADATA_FIELD_LENGTH, "The category of the campaign is too long");
+
+			let action = (&b"update_metadata"[..], &metadata).using_encoded(<T as system::Trait>::Hashing::hash);
+			if !Self::approve_manager_action(campaign_id, sender, action)? {
+				return Ok(());
+			}
+
+			if metadata == CampaignMetadata::default() {
+				<Metadata<T>>::remove(&campaign_id);
+			} else {
+				<Metadata<T>>::insert(&campaign_id, metadata);
+			}
+
+			Self::deposit_event(RawEvent::MetadataUpdated(campaign_id));
+			Ok(())
 		}
-		// add the nonce
-		<Nonce<T>>::mutate(|n| *n += 1);
 
-		Ok(())
-	}
+		/// push out the expiry of a campaign which is still raising money
+		fn extend_campaign(origin, campaign_id: T::Hash, new_expiry: T::BlockNumber) -> Result {
+			let sender = ensure_signed(origin)?;
 
-	//The investor had invested the project before
-	fn invest_before(
-		sender: T::AccountId,
-		campaign_id: T::Hash,
-		invest_amount: T::Balance,
-	) -> Result {
-		ensure!(<Campaigns<T>>::exists(campaign_id),"The Campaign exist does not exist");
-		// ensure the investor has enough money
-		ensure!(
-			<balances::Module<T>>::free_balance(sender.clone()) >= invest_amount,
-			"You don't have enough free balance to invest on this campaign"
-		);
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			let mut campaign = Self::campaign(&campaign_id);
+			ensure!(Self::is_manager(campaign_id, &sender), "Only the campaign managers can extend the campaign");
+			ensure!(campaign.campaign_status == CampaignStatus::Funding, "This campaign is not raising money anymore.");
+			ensure!(<system::Module<T>>::block_number() < campaign.campaign_expiry, "This campaign is expired.");
 
-		let campaign = Self::campaign(&campaign_id);
-		ensure!(
-			<system::Module<T>>::block_number() < campaign.campaign_expiry,
-			"This Campaign expired."
-		);
+			// ensuring validation of the new expiry
+			ensure!(new_expiry > campaign.campaign_expiry, "The new expiry has to be greater than the current expiry");
+			ensure!(new_expiry <= <system::Module<T>>::block_number() + Self::campaign_period_limit(), "The expiry has be lower than the limit block number");
+			// the milestones have to stay after the expiry
+			if let Some(first_milestone) = Self::milestones_of(&campaign_id).first() {
+				ensure!(first_milestone.deadline > new_expiry, "The new expiry has to be before the deadline of the first milestone");
+			}
 
-		// reserve the amount of money
-		<balances::Module<T>>::reserve(&sender, invest_amount)?;
+			let action = (&b"extend_campaign"[..], new_expiry).using_encoded(<T as system::Trait>::Hashing::hash);
+			if !Self::approve_manager_action(campaign_id, sender, action)? {
+				return Ok(());
+			}
 
-		let amount_of_investor_on_campaign =
-			Self::invest_amount_of((campaign_id.clone(), sender.clone()));
-		let new_amount_of_investor_on_campaign =
-			amount_of_investor_on_campaign + invest_amount.clone();
+			// move the campaign to the bucket of its new expiry
+			<CampaignsByBlockNumber<T>>::mutate(campaign.campaign_expiry, |campaigns| campaigns.retain(|c| *c != campaign_id));
+			<CampaignsByBlockNumber<T>>::mutate(new_expiry, |campaigns| campaigns.push(campaign_id));
 
-		<InvestAmount<T>>::insert(
-			(campaign_id, sender),
-			new_amount_of_investor_on_campaign.clone(),
-		);
+			campaign.campaign_expiry = new_expiry;
+			<Campaigns<T>>::insert(&campaign_id, campaign);
 
-		// get the total amount of the project and add invest_amount
-		let amount_of_campaign = Self::total_amount_of_campaign(&campaign_id);
-		let new_amount_of_campaign = amount_of_campaign + invest_amount;
+			Self::deposit_event(RawEvent::CampaignExtended(campaign_id, new_expiry));
+			Ok(())
+		}
 
-		// change the total amount of the project has collected
-		<CampaignSupportedAmount<T>>::insert(&campaign_id, new_amount_of_campaign);
+		/// change the target money of a campaign as long as nobody but its managers invested in it
+		fn update_target(origin, campaign_id: T::Hash, new_target: BalanceOf<T>) -> Result {
+			let sender = ensure_signed(origin)?;
 
-		Ok(())
-	}
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			let mut campaign = Self::campaign(&campaign_id);
+			ensure!(Self::is_manager(campaign_id, &sender), "Only the campaign managers can update the target");
+			ensure!(campaign.campaign_status == CampaignStatus::Funding, "This campaign is not raising money anymore.");
+			ensure!(<system::Module<T>>::block_number() < campaign.campaign_expiry, "This campaign is expired.");
+			ensure!(
+				Self::invest_accounts(&campaign_id).iter().all(|investor| Self::is_manager(campaign_id, investor)),
+				"The target can't be changed once somebody invested in the campaign"
+			);
 
-	// The investor doesn't invest the project before
-	fn not_invest_before(
-		sender: T::AccountId,
-		campaign_id: T::Hash,
-		invest_amount: T::Balance,
-	) -> Result {
-		ensure!(<Campaigns<T>>::exists(campaign_id),"The campaign does not exist");
-		// ensure that the investor has enough money
-		ensure!(
-			<balances::Module<T>>::free_balance(sender.clone()) >= invest_amount,
-			"You don't have enough free balance for investing for this campaign"
-		);
+			ensure!(new_target >= Self::minimum_target(), "The target money is lower than the minimum target");
+			ensure!(new_target >= Self::total_amount_of_campaign(&campaign_id), "You already have enough money");
+			Self::ensure_valid_stretch_goals(&Self::stretch_goals_of(&campaign_id), new_target)?;
 
-		// get the number of projects that the investor had invested and add it
-		let invested_campaign_count = Self::invested_campaign_count(&sender);
-		let new_invested_campaign_count = invested_campaign_count
-			.checked_add(1)
-			.ok_or("Overflow adding a new invested Campaign")?;
+			let action = (&b"update_target"[..], new_target).using_encoded(<T as system::Trait>::Hashing::hash);
+			if !Self::approve_manager_action(campaign_id, sender, action)? {
+				return Ok(());
+			}
 
-		let campaign = Self::campaign(&campaign_id);
-		ensure!(<system::Module<T>>::block_number() < campaign.campaign_expiry,"This campaign is expired.");
+			campaign.campaign_target_money = new_target;
+			<Campaigns<T>>::insert(&campaign_id, campaign);
 
-		// reserve the amount of money
-		<balances::Module<T>>::reserve(&sender, invest_amount)?;
+			Self::deposit_event(RawEvent::TargetUpdated(campaign_id, new_target));
+			Ok(())
+		}
 
-		<InvestAmount<T>>::insert((campaign_id.clone(), sender.clone()), invest_amount.clone());
-		<InvestAccounts<T>>::mutate(&campaign_id, |accounts| accounts.push(sender.clone()));
+		/// hand a campaign over to another account, which has to accept it first if `require_accept` is set
+		fn transfer_campaign(origin, campaign_id: T::Hash, new_owner: T::AccountId, require_accept: bool) -> Result {
+			let sender = ensure_signed(origin)?;
 
-		// add total support count
-		let investor_count = <InvestAccountsCount<T>>::get(&campaign_id);
-		let new_investor_count = investor_count
-			.checked_add(1)
-			.ok_or("Overflow adding the total number of investors of a campaign")?;
-		<InvestAccountsCount<T>>::insert(campaign_id.clone(), new_investor_count);
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			Self::ensure_migrated(campaign_id)?;
+			ensure!(Self::is_manager(campaign_id, &sender), "Only the campaign managers can transfer the campaign");
+			let owner = Self::campaign(&campaign_id).campaign_manager;
+			ensure!(new_owner != owner, "The account already owns this campaign");
+			ensure!(
+				!Self::co_managers_of(&campaign_id).contains(&new_owner),
+				"The campaign can't be transferred to one of its co-managers"
+			);
+			ensure!(
+				!<InvestAmount<T>>::exists((campaign_id, new_owner.clone())),
+				"The campaign can't be transferred to one of its investors"
+			);
 
-		// change the state of invest related fields
-		<InvestedCampaignsArray<T>>::insert(
-			(sender.clone(), invested_campaign_count),
-			campaign_id.clone(),
-		);
-		<InvestedCampaignsCount<T>>::insert(&sender, new_invested_campaign_count);
-		<InvestedCampaignsIndex<T>>::insert(
-			(sender.clone(), campaign_id.clone()),
-			invested_campaign_count,
-		);
+			let action = (&b"transfer_campaign"[..], &new_owner, require_accept).using_encoded(<T as system::Trait>::Hashing::hash);
+			if !Self::approve_manager_action(campaign_id, sender, action)? {
+				return Ok(());
+			}
 
-		// get the total amount of the project and add invest_amount
-		let amount_of_campaign = Self::total_amount_of_campaign(&campaign_id);
-		let new_amount_of_campaign = amount_of_campaign + invest_amount;
+			if require_accept {
+				<PendingCampaignOwner<T>>::insert(&campaign_id, new_owner.clone());
+				Self::deposit_event(RawEvent::CampaignTransferProposed(campaign_id, owner, new_owner));
+				Ok(())
+			} else {
+				Self::change_owner(campaign_id, owner, new_owner)
+			}
+		}
 
+		/// accept a campaign which is being handed over to the sender
+		fn accept_campaign(origin, campaign_id: T::Hash) -> Result {
+			let sender = ensure_signed(origin)?;
 
-		<CampaignSupportedAmount<T>>::insert(&campaign_id, new_amount_of_campaign);
+			let pending_owner = Self::pending_owner_of_campaign(&campaign_id).ok_or("The campaign is not being transferred")?;
+			ensure!(pending_owner == sender, "The campaign is not being transferred to you");
+			Self::ensure_migrated(campaign_id)?;
+			ensure!(
+				!Self::co_managers_of(&campaign_id).contains(&sender),
+				"The campaign can't be transferred to one of its co-managers"
+			);
+			ensure!(
+				!<InvestAmount<T>>::exists((campaign_id, sender.clone())),
+				"The campaign can't be transferred to one of its investors"
+			);
+
+			let owner = Self::campaign(&campaign_id).campaign_manager;
+			Self::change_owner(campaign_id, owner, sender)
+		}
+
+		/// set the co-managers of a campaign and the number of managers who have to approve its privileged actions
+		fn set_managers(origin, campaign_id: T::Hash, co_managers: Vec<T::AccountId>, threshold: u32) -> Result {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			ensure!(Self::is_manager(campaign_id, &sender), "Only the campaign managers can change the managers");
+			ensure!(co_managers.len() <= T::MAX_CO_MANAGERS_PER_CAMPAIGN, "Too many co-managers for a campaign");
+			ensure!(
+				threshold >= 1 && threshold as usize <= co_managers.len() + 1,
+				"The threshold has to be between one and the number of managers"
+			);
+
+			let manager = Self::campaign(&campaign_id).campaign_manager;
+			for (index, co_manager) in co_managers.iter().enumerate() {
+				ensure!(*co_manager != manager, "The campaign manager can't be a co-manager");
+				ensure!(!co_managers[..index].contains(co_manager), "The co-managers have to be distinct");
+				ensure!(
+					!<InvestAmount<T>>::exists((campaign_id, co_manager.clone())),
+					"An investor of the campaign can't be a co-manager"
+				);
+			}
+
+			let action = (&b"set_managers"[..], &co_managers, threshold).using_encoded(<T as system::Trait>::Hashing::hash);
+			if !Self::approve_manager_action(campaign_id, sender, action)? {
+				return Ok(());
+			}
+
+			// The approvals given under the previous managers don't count anymore
+			Self::clear_manager_approvals(campaign_id);
+
+			let co_manager_count = co_managers.len() as u32;
+			if co_managers.is_empty() {
+				<CoManagers<T>>::remove(&campaign_id);
+				<ManagerThreshold<T>>::remove(&campaign_id);
+			} else {
+				<CoManagers<T>>::insert(&campaign_id, co_managers);
+				<ManagerThreshold<T>>::insert(&campaign_id, threshold);
+			}
+
+			Self::deposit_event(RawEvent::ManagersChanged(campaign_id, co_manager_count, threshold));
+			Ok(())
+		}
+
+		/// cancel a campaign that is still raising money, its investors can claim back their investments
+		fn cancel_campaign(origin, campaign_id: T::Hash) -> Result {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			Self::ensure_migrated(campaign_id)?;
+			let campaign = Self::campaign(&campaign_id);
+			ensure!(Self::is_manager(campaign_id, &sender), "Only the campaign managers can cancel the campaign");
+			ensure!(campaign.campaign_status == CampaignStatus::Funding, "Only a campaign which is still raising money can be cancelled");
+
+			let action = <T as system::Trait>::Hashing::hash(b"cancel_campaign");
+			if !Self::approve_manager_action(campaign_id, sender, action)? {
+				return Ok(());
+			}
+
+			// The creation deposit stays reserved until the hold is over, the campaign can still be slashed until then
+			Self::cancel(campaign_id);
+			Ok(())
+		}
+
+		/// slash the creation deposit of a campaign flagged as spam or fraud and cancel it
+		fn slash_campaign(origin, campaign_id: T::Hash) -> Result {
+			ensure_root(origin)?;
+
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			Self::ensure_migrated(campaign_id)?;
+			let campaign = Self::campaign(&campaign_id);
+			let cancelled = campaign.campaign_status == CampaignStatus::Cancelled;
+			ensure!(
+				campaign.campaign_status == CampaignStatus::Funding || (cancelled && <CreationDepositOf<T>>::exists(&campaign_id)),
+				"Only a campaign which is still raising money or holds its creation deposit can be slashed"
+			);
+
+			// The slashed deposit goes to the treasury, or is burned if the treasury can't receive it
+			let deposit = Self::creation_deposit_of(&campaign_id);
+			let manager = campaign.campaign_manager;
+			if T::Currency::repatriate_reserved(&manager, &Self::treasury_account(), deposit).is_err() {
+				let _ = T::Currency::slash_reserved(&manager, deposit);
+			}
+			<CreationDepositOf<T>>::remove(&campaign_id);
+
+			Self::deposit_event(RawEvent::CampaignSlashed(campaign_id, deposit));
+			if !cancelled {
+				Self::cancel(campaign_id);
+			}
+			Ok(())
+		}
+
+		/// return the creation deposit of a cancelled campaign to its manager once the hold is over
+		fn release_cancelled_deposit(origin, campaign_id: T::Hash) -> Result {
+			let _ = ensure_signed(origin)?;
+
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			ensure!(Self::campaign_status(&campaign_id) == CampaignStatus::Cancelled, "The campaign is not cancelled");
+			ensure!(<CreationDepositOf<T>>::exists(&campaign_id), "The campaign holds no creation deposit");
+			ensure!(
+				<system::Module<T>>::block_number() >= Self::status_changed_at(&campaign_id) + Self::creation_deposit_hold(),
+				"The hold of the creation deposit is not over"
+			);
+
+			let deposit = Self::creation_deposit_of(&campaign_id);
+			Self::release_creation_deposit(campaign_id);
+
+			Self::deposit_event(RawEvent::CreationDepositReleased(campaign_id, deposit));
+			Ok(())
+		}
+
+		/// submit the next milestone of a succeeded campaign, its money is released after the review period
+		fn submit_milestone(origin, campaign_id: T::Hash) -> Result {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			let campaign = Self::campaign(&campaign_id);
+			ensure!(Self::is_manager(campaign_id, &sender), "Only the campaign managers can submit a milestone");
+			ensure!(campaign.campaign_status == CampaignStatus::Succeeded, "Only a succeeded campaign can submit a milestone");
+			ensure!(Self::milestone_submission(&campaign_id).is_none(), "A submitted milestone is still under review");
+
+			let milestones = Self::milestones_of(&campaign_id);
+			let milestone_index = Self::next_milestone(&campaign_id);
+			let milestone = milestones.get(milestone_index as usize).ok_or("The campaign has no milestone left to submit")?;
+
+			let block_number = <system::Module<T>>::block_number();
+			ensure!(block_number <= milestone.deadline, "The deadline of the milestone has passed");
+
+			let action = (&b"submit_milestone"[..], milestone_index).using_encoded(<T as system::Trait>::Hashing::hash);
+			if !Self::approve_manager_action(campaign_id, sender, action)? {
+				return Ok(());
+			}
+
+			<MilestoneSubmission<T>>::insert(&campaign_id, (milestone_index, block_number));
+
+			Self::deposit_event(RawEvent::MilestoneSubmitted(campaign_id, milestone_index, block_number));
+			Ok(())
+		}
+
+		/// release the money of a submitted milestone to the campaign manager once its review period is over
+		fn release_milestone(origin, campaign_id: T::Hash) -> Result {
+			let _ = ensure_signed(origin)?;
+
+			let (milestone_index, submitted_at) = Self::milestone_submission(&campaign_id)
+				.ok_or("The campaign has no submitted milestone")?;
+			ensure!(
+				<system::Module<T>>::block_number() >= submitted_at + T::MILESTONE_REVIEW_PERIOD,
+				"The submitted milestone is still under review"
+			);
+
+			Self::release_milestone_funds(campaign_id, milestone_index)
+		}
+
+		/// vote on the submitted milestone of a campaign with the weight of the invested money
+		fn vote_milestone(origin, campaign_id: T::Hash, approve: bool) -> Result {
+			let sender = ensure_signed(origin)?;
+
+			let (milestone_index, submitted_at) = Self::milestone_submission(&campaign_id)
+				.ok_or("The campaign has no submitted milestone")?;
+			ensure!(
+				<system::Module<T>>::block_number() < submitted_at + T::MILESTONE_REVIEW_PERIOD,
+				"The voting period of the milestone is over"
+			);
+
+			ensure!(!Self::is_manager(campaign_id, &sender), "You can't vote on your own milestone");
+			let invest_balance = Self::invest_amount_of((campaign_id, sender.clone()));
+			ensure!(invest_balance > BalanceOf::<T>::sa(0), "Only investors of the campaign can vote");
+
+			// An investor may change his/her mind, take back the previous vote first
+			let vote_key = (campaign_id, milestone_index, sender.clone());
+			let (mut approvals, mut rejections) = Self::milestone_tally((campaign_id, milestone_index));
+			match Self::milestone_vote_of(&vote_key) {
+				Some(true) => approvals = approvals - invest_balance,
+				Some(false) => rejections = rejections - invest_balance,
+				None => {}
+			}
+			if approve {
+				approvals = approvals + invest_balance;
+			} else {
+				rejections = rejections + invest_balance;
+			}
+			<MilestoneVotes<T>>::insert(&vote_key, approve);
+			<MilestoneTally<T>>::insert((campaign_id, milestone_index), (approvals, rejections));
+
+			Self::deposit_event(RawEvent::MilestoneVoted(campaign_id, milestone_index, sender, approve, invest_balance));
+
+			// Enough investors rejected the milestone, give them back what is left in escrow. Nobody votes
+			// with the match of the funding rounds, so only the invested money counts.
+			let amount_of_investment = Self::total_amount_of_campaign(&campaign_id) - Self::matched_amount_of(&campaign_id);
+			if rejections * BalanceOf::<T>::sa(100) >= amount_of_investment * BalanceOf::<T>::sa(T::MILESTONE_REJECTION_THRESHOLD) {
+				Self::deposit_event(RawEvent::MilestoneRejected(campaign_id, milestone_index));
+				Self::refund_escrow(campaign_id);
+			}
+
+			Ok(())
+		}
+
+		/// refund the escrowed money of a campaign to its investors when the next milestone missed its deadline
+		fn refund_missed_milestone(origin, campaign_id: T::Hash) -> Result {
+			let _ = ensure_signed(origin)?;
+
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			let campaign = Self::campaign(&campaign_id);
+			ensure!(campaign.campaign_status == CampaignStatus::Succeeded, "Only a succeeded campaign can be refunded");
+			ensure!(Self::milestone_submission(&campaign_id).is_none(), "A submitted milestone is still under review");
+
+			let milestones = Self::milestones_of(&campaign_id);
+			let milestone = milestones.get(Self::next_milestone(&campaign_id) as usize)
+				.ok_or("The campaign has no milestone left")?;
+			ensure!(<system::Module<T>>::block_number() > milestone.deadline, "The deadline of the milestone has not passed yet");
+
+			Self::refund_escrow(campaign_id);
+			Ok(())
+		}
+
+		/// set the maximum number of blocks between the creation and the expiry of a campaign
+		fn set_campaign_period_limit(origin, campaign_period_limit: T::BlockNumber) -> Result {
+			ensure_root(origin)?;
+
+			<CampaignPeriodLimit<T>>::put(campaign_period_limit);
+
+			Self::deposit_event(RawEvent::CampaignPeriodLimitChanged(campaign_period_limit));
+			Ok(())
+		}
+
+		/// set the lowest target money of a new campaign
+		fn set_minimum_target(origin, minimum_target: BalanceOf<T>) -> Result {
+			ensure_root(origin)?;
+
+			<MinimumTarget<T>>::put(minimum_target);
+
+			Self::deposit_event(RawEvent::MinimumTargetChanged(minimum_target));
+			Ok(())
+		}
+
+		/// set the lowest amount of money for a single investment
+		fn set_minimum_investment(origin, minimum_investment: BalanceOf<T>) -> Result {
+			ensure_root(origin)?;
+
+			<MinimumInvestment<T>>::put(minimum_investment);
+
+			Self::deposit_event(RawEvent::MinimumInvestmentChanged(minimum_investment));
+			Ok(())
+		}
+
+		/// set the money reserved from the manager for the storage of a new campaign
+		fn set_storage_deposit(origin, storage_deposit: BalanceOf<T>) -> Result {
+			ensure_root(origin)?;
+
+			<StorageDeposit<T>>::put(storage_deposit);
+
+			Self::deposit_event(RawEvent::StorageDepositChanged(storage_deposit));
+			Ok(())
+		}
+
+		/// set the money reserved from the manager when creating a new campaign
+		fn set_creation_deposit(origin, creation_deposit: BalanceOf<T>) -> Result {
+			ensure_root(origin)?;
+
+			<CreationDeposit<T>>::put(creation_deposit);
+
+			Self::deposit_event(RawEvent::CreationDepositChanged(creation_deposit));
+			Ok(())
+		}
+
+		/// set the number of blocks the creation deposit of a cancelled campaign stays reserved
+		fn set_creation_deposit_hold(origin, creation_deposit_hold: T::BlockNumber) -> Result {
+			ensure_root(origin)?;
+
+			<CreationDepositHold<T>>::put(creation_deposit_hold);
+
+			Self::deposit_event(RawEvent::CreationDepositHoldChanged(creation_deposit_hold));
+			Ok(())
+		}
+
+		/// set the share of the money of succeeded campaigns which goes to the treasury account
+		fn set_platform_fee(origin, platform_fee: Perbill) -> Result {
+			ensure_root(origin)?;
+
+			<PlatformFee<T>>::put(platform_fee);
+
+			Self::deposit_event(RawEvent::PlatformFeeChanged(platform_fee));
+			Ok(())
+		}
+
+		/// set the account receiving the platform fee
+		fn set_treasury_account(origin, treasury_account: T::AccountId) -> Result {
+			ensure_root(origin)?;
+
+			<TreasuryAccount<T>>::put(treasury_account.clone());
+
+			Self::deposit_event(RawEvent::TreasuryAccountChanged(treasury_account));
+			Ok(())
+		}
+
+		/// create a quadratic funding round ending at the given block
+		fn create_round(origin, end: T::BlockNumber) -> Result {
+			ensure_root(origin)?;
+
+			ensure!(end > <system::Module<T>>::block_number(), "The end of the round has to be greater than the current block number");
+
+			let round_id = Self::round_count();
+			let new_round_count = round_id.checked_add(1).ok_or("Overflow adding a new round")?;
+
+			<Rounds<T>>::insert(round_id, FundingRound { matching_pool: BalanceOf::<T>::sa(0), end });
+			<RoundsByBlockNumber<T>>::mutate(end, |rounds| rounds.push(round_id));
+			<RoundCount<T>>::put(new_round_count);
+
+			Self::deposit_event(RawEvent::RoundCreated(round_id, end));
+			Ok(())
+		}
+
+		/// add money to the matching pool of a funding round
+		fn fund_round(origin, round_id: u64, amount: BalanceOf<T>) -> Result {
+			let sender = ensure_signed(origin)?;
+
+			Self::add_to_matching_pool(round_id, sender, amount)
+		}
+
+		/// add money of the treasury account to the matching pool of a funding round
+		fn fund_round_from_treasury(origin, round_id: u64, amount: BalanceOf<T>) -> Result {
+			ensure_root(origin)?;
+
+			Self::add_to_matching_pool(round_id, Self::treasury_account(), amount)
+		}
+
+		/// let a campaign which is still raising money take part in a funding round
+		fn join_round(origin, campaign_id: T::Hash, round_id: u64) -> Result {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			Self::ensure_migrated(campaign_id)?;
+			let campaign = Self::campaign(&campaign_id);
+			ensure!(Self::is_manager(campaign_id, &sender), "Only the campaign managers can join a round");
+			ensure!(campaign.campaign_status == CampaignStatus::Funding, "This campaign is not raising money anymore.");
+			ensure!(Self::round_of_campaign(&campaign_id).is_none(), "The campaign already takes part in a round");
+			ensure!(Self::asset_of_campaign(&campaign_id).is_none(), "Only a campaign in the native currency can join a round");
+
+			let round = Self::round(round_id).ok_or("The round does not exist")?;
+			// The match is added to the raised money, so the campaign has to be raising money when the round ends
+			ensure!(campaign.campaign_expiry > round.end, "The campaign has to expire after the end of the round");
+			ensure!(
+				Self::campaigns_of_round(round_id).len() < T::MAX_CAMPAIGNS_PER_ROUND,
+				"Too many campaigns in the round"
+			);
+
+			let action = (&b"join_round"[..], round_id).using_encoded(<T as system::Trait>::Hashing::hash);
+			if !Self::approve_manager_action(campaign_id, sender, action)? {
+				return Ok(());
+			}
+
+			<RoundCampaigns<T>>::mutate(round_id, |campaigns| campaigns.push(campaign_id));
+			<RoundOfCampaign<T>>::insert(&campaign_id, round_id);
+
+			Self::deposit_event(RawEvent::RoundJoined(campaign_id, round_id));
+			Ok(())
+		}
+
+		/// claim the raised money of a succeeded campaign
+		fn claim_funds(origin, campaign_id: T::Hash) -> Result {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			let campaign = Self::campaign(&campaign_id);
+			ensure!(Self::is_manager(campaign_id, &sender), "Only the campaign managers can claim the funds");
+			ensure!(campaign.campaign_status == CampaignStatus::PayoutPending, "The campaign has no funds to claim");
+			ensure!(!<VestingSchedules<T>>::exists(&campaign_id), "The funds of the campaign vest, claim them with claim_vested");
+
+			let action = <T as system::Trait>::Hashing::hash(b"claim_funds");
+			if !Self::approve_manager_action(campaign_id, sender, action)? {
+				return Ok(());
+			}
+
+			// The money always goes to the campaign manager, whoever gave the last approval
+			let payout = Self::escrow_balance(&campaign_id);
+			Self::transfer_pledge(campaign_id, &T::escrow_account(), &campaign.campaign_manager, payout)?;
+			<EscrowBalance<T>>::insert(&campaign_id, BalanceOf::<T>::sa(0));
+			Self::set_campaign_status(campaign_id, CampaignStatus::Succeeded);
+
+			Self::deposit_event(RawEvent::FundsClaimed(campaign_id, campaign.campaign_manager, payout));
+			Ok(())
+		}
+
+		/// claim the raised money of a succeeded campaign which has vested so far
+		fn claim_vested(origin, campaign_id: T::Hash) -> Result {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			let campaign = Self::campaign(&campaign_id);
+			ensure!(Self::is_manager(campaign_id, &sender), "Only the campaign managers can claim the funds");
+			ensure!(campaign.campaign_status == CampaignStatus::PayoutPending, "The campaign has no funds to claim");
+
+			let payout = Self::claimable_vested(campaign_id)?;
+			ensure!(payout > BalanceOf::<T>::sa(0), "No funds of the campaign have vested yet");
+
+			let action = <T as system::Trait>::Hashing::hash(b"claim_vested");
+			if !Self::approve_manager_action(campaign_id, sender, action)? {
+				return Ok(());
+			}
+
+			Self::transfer_pledge(campaign_id, &T::escrow_account(), &campaign.campaign_manager, payout)?;
+			let escrowed = Self::escrow_balance(&campaign_id) - payout;
+			<EscrowBalance<T>>::insert(&campaign_id, escrowed);
+			if escrowed == BalanceOf::<T>::sa(0) {
+				Self::set_campaign_status(campaign_id, CampaignStatus::Succeeded);
+			}
+
+			Self::deposit_event(RawEvent::FundsClaimed(campaign_id, campaign.campaign_manager, payout));
+			Ok(())
+		}
+
+		/// claim back the share of the investment in a failed, cancelled or refunded campaign
+		fn claim_refund(origin, campaign_id: T::Hash) -> Result {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			Self::ensure_migrated(campaign_id)?;
+			let status = Self::campaign(&campaign_id).campaign_status;
+			ensure!(
+				status == CampaignStatus::Failed || status == CampaignStatus::Cancelled || status == CampaignStatus::Refunded,
+				"The campaign has nothing to refund"
+			);
+			ensure!(<InvestAmount<T>>::exists((campaign_id, sender.clone())), "You have not invested in this campaign");
+			ensure!(!Self::is_settled((campaign_id, sender.clone())), "You have already claimed your refund");
+
+			Self::settle_refund(campaign_id, sender)
+		}
+
+		/// claim the backer shares of the investment in a succeeded campaign
+		fn claim_shares(origin, campaign_id: T::Hash) -> Result {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			Self::ensure_migrated(campaign_id)?;
+			ensure!(Self::issues_shares(campaign_id), "The campaign has no shares to issue");
+			ensure!(<InvestAmount<T>>::exists((campaign_id, sender.clone())), "You have not invested in this campaign");
+			ensure!(!Self::has_shares((campaign_id, sender.clone())), "You have already claimed your shares");
+
+			Self::issue_shares(campaign_id, sender)
+		}
+
+		/// remove a settled campaign from storage after the grace period and return its storage deposit,
+		/// a campaign with many investors takes several calls
+		fn reap_campaign(origin, campaign_id: T::Hash) -> Result {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			ensure!(Self::campaigns_migrated(), "The campaigns are still being migrated");
+			ensure!(
+				<system::Module<T>>::block_number() >= Self::status_changed_at(&campaign_id) + T::REAP_GRACE_PERIOD,
+				"The grace period of the campaign is not over"
+			);
+			Self::ensure_settled(campaign_id)?;
+			ensure!(
+				!<CreationDepositOf<T>>::exists(&campaign_id)
+					|| <system::Module<T>>::block_number() >= Self::status_changed_at(&campaign_id) + Self::creation_deposit_hold(),
+				"The hold of the creation deposit is not over"
+			);
+
+			// The investors of a campaign are reaped a batch per call, the campaign itself with the last batch
+			if !Self::reap_investors(campaign_id) {
+				return Ok(());
+			}
+			Self::reap(campaign_id)?;
+
+			Self::deposit_event(RawEvent::CampaignReaped(campaign_id, sender));
+			Ok(())
+		}
+
+		/// finalize a campaign which reached its target before its expiry, if it was created allowing it
+		fn close_early(origin, campaign_id: T::Hash) -> Result {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<Campaigns<T>>::exists(campaign_id), "The campaign does not exist");
+			Self::ensure_migrated(campaign_id)?;
+			let campaign = Self::campaign(&campaign_id);
+			ensure!(Self::is_manager(campaign_id, &sender), "Only the campaign managers can close the campaign");
+			ensure!(Self::early_close_allowed(&campaign_id), "The campaign can't be closed early");
+			ensure!(campaign.campaign_status == CampaignStatus::Funding, "This campaign is not raising money anymore.");
+			ensure!(<system::Module<T>>::block_number() < campaign.campaign_expiry, "This campaign is expired.");
+			ensure!(
+				Self::total_amount_of_campaign(&campaign_id) >= campaign.campaign_target_money,
+				"The campaign has not reached its target yet"
+			);
+
+			let action = <T as system::Trait>::Hashing::hash(b"close_early");
+			if !Self::approve_manager_action(campaign_id, sender, action)? {
+				return Ok(());
+			}
+
+			// The campaign must not be finalized again at its expiry
+			<CampaignsByBlockNumber<T>>::mutate(campaign.campaign_expiry, |campaigns| campaigns.retain(|c| *c != campaign_id));
+			Self::finalize(campaign_id);
+			Ok(())
+		}
+
+		fn on_initialize() {
+			// The settlement queue gets what the migration leaves of the budget
+			let mut budget = T::SETTLEMENTS_PER_BLOCK;
+			if !Self::campaigns_migrated() {
+				budget = Self::migrate_campaigns(budget);
+			}
+
+			Self::process_settlement_queue(budget);
+		}
+
+		fn on_finalize() {
+		// get all the Campaign present in the block
+			let block_number = <system::Module<T>>::block_number();
+			let campaign_hash = Self::campaign_expire_at(block_number);
+
+			// The rounds end before the campaigns, which always expire after their round
+			for round_id in Self::rounds_end_at(block_number) {
+				Self::close_round(round_id);
+			}
+			<RoundsByBlockNumber<T>>::remove(block_number);
+
+			for campaign_id in &campaign_hash{
+				// A campaign which is still being migrated expires in the first block after it is migrated
+				if Self::ensure_migrated(*campaign_id).is_err() {
+					let next_block = block_number + T::BlockNumber::sa(1);
+					<Campaigns<T>>::mutate(campaign_id, |campaign| campaign.campaign_expiry = next_block);
+					<CampaignsByBlockNumber<T>>::mutate(next_block, |campaigns| campaigns.push(*campaign_id));
+					continue;
+				}
+				Self::finalize(*campaign_id);
+			}
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	fn mint(
+		sender: T::AccountId,
+		campaign_id: T::Hash,
+		expiry: T::BlockNumber,
+		support_money: BalanceOf<T>,
+		new_campaign: Campaign<T::Hash, T::AccountId, BalanceOf<T>, T::BlockNumber>,
+	) -> Result {
+		//Verify first Execute Last
+		let campaign_count = Self::campaign_count();
+		let new_campaign_count = campaign_count
+			.checked_add(1)
+			.ok_or("Overflow adding a new Campaign")?;
+
+		// updating the global states
+		<StatusOfCampaign<T>>::insert(campaign_id.clone(), new_campaign.campaign_status);
+		<Campaigns<T>>::insert(campaign_id.clone(), new_campaign.clone());
+		<CampaignOwner<T>>::insert(campaign_id.clone(), sender.clone());
+
+		<CampaignsByBlockNumber<T>>::mutate(expiry, |campaigns| campaigns.push(campaign_id.clone()));
+
+		<AllCampaignArray<T>>::insert(&campaign_count, campaign_id.clone());
+		<AllCampaignCount<T>>::put(new_campaign_count);
+		<AllCampaignIndex<T>>::insert(campaign_id.clone(), campaign_count);
+
+		Self::add_owned_campaign(sender.clone(), campaign_id.clone())?;
+
+		if support_money > BalanceOf::<T>::sa(0) {
+			Self::not_invest_before(sender.clone(), campaign_id.clone(), support_money.clone())?;
+		}
+		// add the nonce
+		<Nonce<T>>::mutate(|n| *n += 1);
+
+		Ok(())
+	}
+
+	//The investor had invested the project before
+	fn invest_before(
+		sender: T::AccountId,
+		campaign_id: T::Hash,
+		invest_amount: BalanceOf<T>,
+	) -> Result {
+		ensure!(<Campaigns<T>>::exists(campaign_id),"The Campaign exist does not exist");
+		// ensure the investor has enough money
+		ensure!(
+			Self::free_balance_of(campaign_id, &sender) >= invest_amount,
+			"You don't have enough free balance to invest on this campaign"
+		);
+
+		let campaign = Self::campaign(&campaign_id);
+		ensure!(campaign.campaign_status == CampaignStatus::Funding, "This Campaign is not raising money anymore.");
+		ensure!(
+			<system::Module<T>>::block_number() < campaign.campaign_expiry,
+			"This Campaign expired."
+		);
+
+		// move the money into the escrow account until the campaign is settled
+		Self::transfer_pledge(campaign_id, &sender, &T::escrow_account(), invest_amount)?;
+		<EscrowBalance<T>>::mutate(&campaign_id, |balance| *balance = *balance + invest_amount);
+
+		let amount_of_investor_on_campaign =
+			Self::invest_amount_of((campaign_id.clone(), sender.clone()));
+		let new_amount_of_investor_on_campaign =
+			amount_of_investor_on_campaign + invest_amount.clone();
+
+		<InvestAmount<T>>::insert(
+			(campaign_id, sender),
+			new_amount_of_investor_on_campaign.clone(),
+		);
+
+		// get the total amount of the project and add invest_amount
+		let amount_of_campaign = Self::total_amount_of_campaign(&campaign_id);
+		let new_amount_of_campaign = amount_of_campaign + invest_amount;
+
+		// change the total amount of the project has collected
+		<CampaignSupportedAmount<T>>::insert(&campaign_id, new_amount_of_campaign);
+
+		Ok(())
+	}
+
+	// The investor doesn't invest the project before
+	fn not_invest_before(
+		sender: T::AccountId,
+		campaign_id: T::Hash,
+		invest_amount: BalanceOf<T>,
+	) -> Result {
+		ensure!(<Campaigns<T>>::exists(campaign_id),"The campaign does not exist");
+		// ensure that the investor has enough money
+		ensure!(
+			Self::free_balance_of(campaign_id, &sender) >= invest_amount,
+			"You don't have enough free balance for investing for this campaign"
+		);
+
+		// get the number of projects that the investor had invested and add it
+		let invested_campaign_count = Self::invested_campaign_count(&sender);
+		let new_invested_campaign_count = invested_campaign_count
+			.checked_add(1)
+			.ok_or("Overflow adding a new invested Campaign")?;
+
+		let campaign = Self::campaign(&campaign_id);
+		ensure!(campaign.campaign_status == CampaignStatus::Funding, "This campaign is not raising money anymore.");
+		ensure!(<system::Module<T>>::block_number() < campaign.campaign_expiry,"This campaign is expired.");
+		ensure!(
+			Self::invest_accounts_count(&campaign_id) < T::MAX_INVESTORS_PER_CAMPAIGN,
+			"Maximum number of investors is reached for this campaign"
+		);
+
+		// move the money into the escrow account until the campaign is settled
+		Self::transfer_pledge(campaign_id, &sender, &T::escrow_account(), invest_amount)?;
+		<EscrowBalance<T>>::mutate(&campaign_id, |balance| *balance = *balance + invest_amount);
+
+		<InvestAmount<T>>::insert((campaign_id.clone(), sender.clone()), invest_amount.clone());
+		<InvestAccounts<T>>::mutate(&campaign_id, |accounts| accounts.push(sender.clone()));
+
+		// add total support count
+		let investor_count = <InvestAccountsCount<T>>::get(&campaign_id);
+		let new_investor_count = investor_count
+			.checked_add(1)
+			.ok_or("Overflow adding the total number of investors of a campaign")?;
+		<InvestAccountsCount<T>>::insert(campaign_id.clone(), new_investor_count);
+
+		// change the state of invest related fields
+		<InvestedCampaignsArray<T>>::insert(
+			(sender.clone(), invested_campaign_count),
+			campaign_id.clone(),
+		);
+		<InvestedCampaignsCount<T>>::insert(&sender, new_invested_campaign_count);
+		<InvestedCampaignsIndex<T>>::insert(
+			(sender.clone(), campaign_id.clone()),
+			invested_campaign_count,
+		);
+
+		// get the total amount of the project and add invest_amount
+		let amount_of_campaign = Self::total_amount_of_campaign(&campaign_id);
+		let new_amount_of_campaign = amount_of_campaign + invest_amount;
+
+
+		<CampaignSupportedAmount<T>>::insert(&campaign_id, new_amount_of_campaign);
+
+		Ok(())
+	}
+	// The milestones must share all of the raised money and be due one after another after the expiry
+	fn ensure_valid_milestones(milestones: &[Milestone<T::BlockNumber>], expiry: T::BlockNumber) -> Result {
+		ensure!(milestones.len() <= T::MAX_MILESTONES_PER_CAMPAIGN, "Too many milestones for a campaign");
+		if milestones.is_empty() {
+			return Ok(());
+		}
+
+		let mut total_percentage: u32 = 0;
+		let mut last_deadline = expiry;
+		for milestone in milestones {
+			ensure!(milestone.percentage > 0, "The percentage of a milestone has to be greater than zero");
+			ensure!(milestone.deadline > last_deadline, "The deadline of a milestone has to be after the expiry and the previous milestone");
+			total_percentage = total_percentage
+				.checked_add(milestone.percentage)
+				.ok_or("Overflow adding the percentages of the milestones")?;
+			last_deadline = milestone.deadline;
+		}
+		ensure!(total_percentage == 100, "The percentages of the milestones have to add up to 100");
+
+		Ok(())
+	}
+
+	fn ensure_valid_reward_tiers(reward_tiers: &[RewardTier<BalanceOf<T>>]) -> Result {
+		ensure!(reward_tiers.len() <= T::MAX_REWARD_TIERS_PER_CAMPAIGN, "Too many reward tiers for a campaign");
+		for tier in reward_tiers {
+			ensure!(tier.min_pledge > BalanceOf::<T>::sa(0), "The minimum pledge of a reward tier has to be greater than zero");
+			ensure!(tier.quantity != Some(0), "The quantity of a reward tier has to be greater than zero");
+		}
+		Ok(())
+	}
+
+	fn ensure_valid_stretch_goals(stretch_goals: &[BalanceOf<T>], target_money: BalanceOf<T>) -> Result {
+		ensure!(stretch_goals.len() <= T::MAX_STRETCH_GOALS_PER_CAMPAIGN, "Too many stretch goals for a campaign");
+		let mut last_goal = target_money;
+		for goal in stretch_goals {
+			ensure!(*goal > last_goal, "The stretch goals have to be ascending and above the target");
+			last_goal = *goal;
+		}
+		Ok(())
+	}
+
+	// Count the stretch goals reached by the money of the campaign, announcing the newly reached ones
+	fn update_stretch_goals(campaign_id: T::Hash) {
+		let stretch_goals = Self::stretch_goals_of(&campaign_id);
+		if stretch_goals.is_empty() {
+			return;
+		}
+
+		let amount_of_campaign = Self::total_amount_of_campaign(&campaign_id);
+		let previously_reached = Self::stretch_goals_reached(&campaign_id);
+		let reached = stretch_goals.iter().take_while(|goal| amount_of_campaign >= **goal).count() as u32;
+
+		for goal_index in previously_reached..reached {
+			Self::deposit_event(RawEvent::StretchGoalReached(campaign_id, goal_index, stretch_goals[goal_index as usize]));
+		}
+		<StretchGoalsReached<T>>::insert(&campaign_id, reached);
+	}
+
+	// The pledge has to reach the reward tier and the tier must not be sold out
+	fn ensure_reward_tier_available(
+		campaign_id: T::Hash,
+		investor: &T::AccountId,
+		tier_index: u32,
+		pledge: BalanceOf<T>,
+	) -> Result {
+		let tiers = Self::reward_tiers_of(&campaign_id);
+		let tier = tiers.get(tier_index as usize).ok_or("The reward tier does not exist")?;
+		ensure!(pledge >= tier.min_pledge, "The pledge is lower than the minimum pledge of the reward tier");
+
+		// An investor who already holds the tier doesn't take another one
+		if Self::claimed_reward_tier_of((campaign_id, investor.clone())) != Some(tier_index) {
+			if let Some(quantity) = tier.quantity {
+				ensure!(
+					Self::reward_tier_claim_count((campaign_id, tier_index)) < quantity,
+					"The reward tier is sold out"
+				);
+			}
+		}
+		Ok(())
+	}
+
+	// Move the claim of an investor to another reward tier, or release it with `None`
+	fn set_reward_tier(campaign_id: T::Hash, investor: T::AccountId, tier: Option<u32>) {
+		let previous = Self::claimed_reward_tier_of((campaign_id, investor.clone()));
+		if previous == tier {
+			return;
+		}
+
+		if let Some(previous_index) = previous {
+			<RewardTierClaimCount<T>>::mutate((campaign_id, previous_index), |count| *count = count.saturating_sub(1));
+			<ClaimedRewardTier<T>>::remove((campaign_id, investor.clone()));
+			Self::deposit_event(RawEvent::RewardTierReleased(campaign_id, investor.clone(), previous_index));
+		}
+		if let Some(tier_index) = tier {
+			<RewardTierClaimCount<T>>::mutate((campaign_id, tier_index), |count| *count = count.saturating_add(1));
+			<ClaimedRewardTier<T>>::insert((campaign_id, investor.clone()), tier_index);
+			Self::deposit_event(RawEvent::RewardTierClaimed(campaign_id, investor, tier_index));
+		}
+	}
+
+	// Transfer the share of a milestone from the escrow account to the campaign manager
+	fn release_milestone_funds(campaign_id: T::Hash, milestone_index: u32) -> Result {
+		let milestones = Self::milestones_of(&campaign_id);
+		let milestone = milestones.get(milestone_index as usize).ok_or("The milestone does not exist")?;
+		let escrowed = Self::escrow_balance(&campaign_id);
+
+		// The last milestone releases everything which is left to avoid rounding dust
+		let release_amount = if milestone_index as usize + 1 == milestones.len() {
+			escrowed
+		} else {
+			// The milestones share the raised money which is left after the platform fee
+			let escrowed_at_success = Self::total_amount_of_campaign(&campaign_id) - Self::platform_fee_of(&campaign_id);
+			let share = escrowed_at_success * BalanceOf::<T>::sa(milestone.percentage as u64) / BalanceOf::<T>::sa(100);
+			if share < escrowed { share } else { escrowed }
+		};
+
+		let manager = Self::campaign(&campaign_id).campaign_manager;
+		Self::transfer_pledge(campaign_id, &T::escrow_account(), &manager, release_amount)?;
+
+		<EscrowBalance<T>>::insert(&campaign_id, escrowed - release_amount);
+		<NextMilestone<T>>::insert(&campaign_id, milestone_index + 1);
+		<MilestoneSubmission<T>>::remove(&campaign_id);
+		// None of the money can be refunded anymore, so the backer shares can be issued
+		if milestone_index as usize + 1 == milestones.len() {
+			<SettlementQueue<T>>::mutate(|queue| queue.push(campaign_id));
+		}
+
+		Self::deposit_event(RawEvent::MilestoneReleased(campaign_id, milestone_index, release_amount));
+		Ok(())
+	}
+
+	// Let the investors claim back the escrowed money of a campaign pro rata to their investments
+	fn refund_escrow(campaign_id: T::Hash) {
+		let escrowed = Self::escrow_balance(&campaign_id);
+		<MilestoneSubmission<T>>::remove(&campaign_id);
+		Self::set_campaign_status(campaign_id, CampaignStatus::Refunded);
+		Self::start_refunds(campaign_id);
+
+		Self::deposit_event(RawEvent::EscrowRefunded(campaign_id, escrowed));
+	}
+
+	// Whether an account is the manager or one of the co-managers of a campaign
+	fn is_manager(campaign_id: T::Hash, account: &T::AccountId) -> bool {
+		Self::campaign(&campaign_id).campaign_manager == *account || Self::co_managers_of(&campaign_id).contains(account)
+	}
+
+	// Record the approval of a manager for an action on a campaign, true once enough managers approved it
+	fn approve_manager_action(campaign_id: T::Hash, manager: T::AccountId, action: T::Hash) -> rstd::result::Result<bool, &'static str> {
+		let threshold = Self::manager_threshold_of(&campaign_id);
+		if threshold <= 1 {
+			return Ok(true);
+		}
+
+		let mut approvals = Self::manager_approvals_of((campaign_id, action));
+		ensure!(!approvals.contains(&manager), "You have already approved this action");
+		approvals.push(manager.clone());
+		let approval_count = approvals.len() as u32;
+
+		if approval_count >= threshold {
+			<ManagerApprovals<T>>::remove((campaign_id, action));
+			<PendingManagerActions<T>>::mutate(&campaign_id, |actions| actions.retain(|a| *a != action));
+		} else {
+			if approval_count == 1 {
+				<PendingManagerActions<T>>::mutate(&campaign_id, |actions| actions.push(action));
+			}
+			<ManagerApprovals<T>>::insert((campaign_id, action), approvals);
+		}
+
+		Self::deposit_event(RawEvent::ManagerActionApproved(campaign_id, action, manager, approval_count));
+		Ok(approval_count >= threshold)
+	}
+
+	// Drop the approvals of every pending action on a campaign
+	fn clear_manager_approvals(campaign_id: T::Hash) {
+		for action in Self::pending_manager_actions_of(&campaign_id) {
+			<ManagerApprovals<T>>::remove((campaign_id, action));
+		}
+		<PendingManagerActions<T>>::remove(&campaign_id);
+	}
+
+	// Move a campaign and the deposits reserved for it from one owner to another
+	fn change_owner(campaign_id: T::Hash, from: T::AccountId, to: T::AccountId) -> Result {
+		let deposits = Self::campaign_deposit(&campaign_id) + Self::creation_deposit_of(&campaign_id);
+		if deposits > BalanceOf::<T>::sa(0) {
+			T::Currency::repatriate_reserved(&from, &to, deposits)?;
+			T::Currency::reserve(&to, deposits)?;
+		}
+
+		Self::remove_owned_campaign(from.clone(), campaign_id)?;
+		Self::add_owned_campaign(to.clone(), campaign_id)?;
+
+		<Campaigns<T>>::mutate(&campaign_id, |campaign| campaign.campaign_manager = to.clone());
+		<CampaignOwner<T>>::insert(&campaign_id, to.clone());
+		<PendingCampaignOwner<T>>::remove(&campaign_id);
+		// The approvals given under the previous owner don't count anymore
+		Self::clear_manager_approvals(campaign_id);
+		// The support money of the previous owner counts towards the quadratic match from now on
+		Self::recount_contribution(campaign_id, &from);
+
+		Self::deposit_event(RawEvent::CampaignTransferred(campaign_id, from, to));
+		Ok(())
+	}
+
+	// Settle the status of a campaign which stops raising money, the money is settled when it is claimed
+	fn finalize(campaign_id: T::Hash) {
+		let block_number = <system::Module<T>>::block_number();
+		let campaign = Self::campaign(&campaign_id);
+		let amount_of_investment = Self::total_amount_of_campaign(&campaign_id);
+		// The campaign finalizes normally, so its manager gets the creation deposit back
+		Self::release_creation_deposit(campaign_id);
+		Self::release_sponsor_offers(campaign_id);
+		// A keep-it-all campaign succeeds with whatever it raised
+		let keeps_all = Self::funding_mode_of(&campaign_id) == FundingMode::KeepItAll
+			&& amount_of_investment > BalanceOf::<T>::sa(0);
+		if amount_of_investment >= campaign.campaign_target_money || keeps_all {
+			// Record the reached stretch goals and keep the rest of the money in escrow
+			Self::update_stretch_goals(campaign_id);
+			let fee = Self::charge_platform_fee(campaign_id);
+			// The money of a campaign with milestones is released milestone by milestone instead
+			let status = if <Milestones<T>>::exists(&campaign_id) {
+				CampaignStatus::Succeeded
+			} else {
+				CampaignStatus::PayoutPending
+			};
+			Self::set_campaign_status(campaign_id, status);
+			if <VestingSchedules<T>>::exists(&campaign_id) {
+				<VestingTotal<T>>::insert(&campaign_id, Self::escrow_balance(&campaign_id));
+			}
+			// The investors who don't claim their backer shares are issued them automatically,
+			// the investors of a campaign with milestones once its last milestone is released
+			if !<Milestones<T>>::exists(&campaign_id) {
+				<SettlementQueue<T>>::mutate(|queue| queue.push(campaign_id));
+			}
+			// deposit the event
+			Self::deposit_event(RawEvent::CampaignFinalized(campaign_id, amount_of_investment, fee, block_number, status));
+		}else{ // every investor can claim back the whole investment
+			// Make the status fail
+			Self::set_campaign_status(campaign_id, CampaignStatus::Failed);
+			Self::start_refunds(campaign_id);
+			// deposit the event
+			Self::deposit_event(RawEvent::CampaignFinalized(campaign_id, amount_of_investment, BalanceOf::<T>::sa(0), block_number, CampaignStatus::Failed));
+		}
+	}
+
+	// The free balance of an account in the currency a campaign raises money in
+	fn free_balance_of(campaign_id: T::Hash, who: &T::AccountId) -> BalanceOf<T> {
+		match Self::asset_of_campaign(&campaign_id) {
+			Some(asset_id) => <assets::Module<T>>::balance_of((asset_id, who.clone())),
+			None => T::Currency::free_balance(who),
+		}
+	}
+
+	// Transfer money in the currency a campaign raises money in
+	fn transfer_pledge(campaign_id: T::Hash, from: &T::AccountId, to: &T::AccountId, amount: BalanceOf<T>) -> Result {
+		match Self::asset_of_campaign(&campaign_id) {
+			Some(asset_id) => <assets::Module<T>>::make_transfer(asset_id, from, to, amount),
+			None if *from == T::escrow_account() => Self::pay_out_of_escrow(to, amount),
+			None => T::Currency::transfer(from, to, amount),
+		}
+	}
+
+	// Transfer native money out of the escrow account. The escrow account keeps an untracked existential
+	// deposit, so paying out tracked money never kills it and destroys the money of the other campaigns.
+	fn pay_out_of_escrow(to: &T::AccountId, amount: BalanceOf<T>) -> Result {
+		let escrow_account = T::escrow_account();
+		ensure!(
+			T::Currency::free_balance(&escrow_account) >= amount + T::Currency::minimum_balance(),
+			"The escrow account can't drop below the existential deposit"
+		);
+		T::Currency::transfer(&escrow_account, to, amount)
+	}
+
+	// The vested money of a campaign which its manager has not claimed yet. The vesting starts when the
+	// campaign is finalized, which is the last status change until all of the money is claimed.
+	fn claimable_vested(campaign_id: T::Hash) -> rstd::result::Result<BalanceOf<T>, &'static str> {
+		let schedule = Self::vesting_schedule_of(&campaign_id).ok_or("The funds of the campaign don't vest")?;
+		let elapsed = <system::Module<T>>::block_number() - Self::status_changed_at(&campaign_id);
+		if elapsed < schedule.cliff {
+			return Ok(BalanceOf::<T>::sa(0));
+		}
+
+		let total = Self::vesting_total_of(&campaign_id);
+		let vested = if elapsed >= schedule.duration {
+			total
+		} else {
+			let elapsed_blocks: u64 = elapsed.as_();
+			let duration_blocks: u64 = schedule.duration.as_();
+			total * BalanceOf::<T>::sa(elapsed_blocks) / BalanceOf::<T>::sa(duration_blocks)
+		};
+		let claimed = total - Self::escrow_balance(&campaign_id);
+		Ok(vested.checked_sub(&claimed).unwrap_or_else(|| BalanceOf::<T>::sa(0)))
+	}
+
+	// Transfer money of an account into the escrow account for the matching pool of a round
+	fn add_to_matching_pool(round_id: u64, funder: T::AccountId, amount: BalanceOf<T>) -> Result {
+		let mut round = Self::round(round_id).ok_or("The round does not exist")?;
+		ensure!(amount > BalanceOf::<T>::sa(0), "The amount has to be greater than zero");
+
+		T::Currency::transfer(&funder, &T::escrow_account(), amount)?;
+		round.matching_pool = round.matching_pool + amount;
+		<Rounds<T>>::insert(round_id, round);
+
+		Self::deposit_event(RawEvent::RoundFunded(round_id, funder, amount));
+		Ok(())
+	}
+
+	// Share the matching pool of a round among its campaigns which are still raising money,
+	// in proportion to the quadratic funding match of each campaign
+	fn close_round(round_id: u64) {
+		let round = match Self::round(round_id) {
+			Some(round) => round,
+			None => return,
+		};
+		let round_campaigns = Self::campaigns_of_round(round_id);
+
+		let mut matches = Vec::new();
+		let mut total_match = BalanceOf::<T>::sa(0);
+		for campaign_id in &round_campaigns {
+			<RoundOfCampaign<T>>::remove(campaign_id);
+			if Self::campaign_status(campaign_id) != CampaignStatus::Funding {
+				continue;
+			}
+			let quadratic_match = Self::quadratic_match(*campaign_id);
+			total_match = total_match + quadratic_match;
+			matches.push((*campaign_id, quadratic_match));
+		}
+
+		let mut distributed = BalanceOf::<T>::sa(0);
+		if total_match > BalanceOf::<T>::sa(0) {
+			for (campaign_id, quadratic_match) in matches {
+				let matched = round.matching_pool
+					.checked_mul(&quadratic_match)
+					.map(|scaled| scaled / total_match)
+					.unwrap_or_else(|| round.matching_pool / total_match * quadratic_match);
+				if matched == BalanceOf::<T>::sa(0) {
+					continue;
+				}
+
+				// The match is raised money like any investment, it stays in the escrow account
+				<CampaignSupportedAmount<T>>::mutate(&campaign_id, |amount| *amount = *amount + matched);
+				<EscrowBalance<T>>::mutate(&campaign_id, |balance| *balance = *balance + matched);
+				<MatchedAmount<T>>::mutate(&campaign_id, |amount| *amount = *amount + matched);
+				distributed = distributed + matched;
+				Self::update_stretch_goals(campaign_id);
+
+				Self::deposit_event(RawEvent::RoundMatched(campaign_id, round_id, matched));
+			}
+		}
+
+		// The rounding dust, or the whole pool if nobody contributed, goes to the treasury
+		let remainder = round.matching_pool - distributed;
+		if remainder > BalanceOf::<T>::sa(0) {
+			let _ = Self::pay_out_of_escrow(&Self::treasury_account(), remainder);
+		}
+
+		<RoundCampaigns<T>>::remove(round_id);
+		<Rounds<T>>::remove(round_id);
+
+		Self::deposit_event(RawEvent::RoundClosed(round_id, distributed, remainder));
+	}
+
+	// The square of the sum of the square roots of the contributions minus the sum of the contributions
+	fn quadratic_match(campaign_id: T::Hash) -> BalanceOf<T> {
+		let (sum_of_roots, sum) = Self::contribution_sums_of(&campaign_id);
+
+		// The square is at most the number of investors times the sum, so it doesn't overflow in practice
+		sum_of_roots
+			.checked_mul(&sum_of_roots)
+			.and_then(|square| square.checked_sub(&sum))
+			.unwrap_or_else(|| BalanceOf::<T>::sa(0))
+	}
+
+	// Update the running sums of the quadratic match of a campaign with the current contribution of an
+	// investor, the contributions of the managers themselves and the sponsor matches don't count
+	fn recount_contribution(campaign_id: T::Hash, investor: &T::AccountId) {
+		let counted = if Self::is_manager(campaign_id, investor) || <SponsorOffers<T>>::exists((campaign_id, investor.clone())) {
+			BalanceOf::<T>::sa(0)
+		} else {
+			Self::invest_amount_of((campaign_id, investor.clone()))
+		};
+		let previous = Self::counted_contribution_of((campaign_id, investor.clone()));
+		if counted == previous {
+			return;
+		}
+
+		<ContributionSums<T>>::mutate(&campaign_id, |(sum_of_roots, sum)| {
+			*sum_of_roots = *sum_of_roots - Self::integer_sqrt(previous) + Self::integer_sqrt(counted);
+			*sum = *sum - previous + counted;
+		});
+		if counted == BalanceOf::<T>::sa(0) {
+			<CountedContribution<T>>::remove((campaign_id, investor.clone()));
+		} else {
+			<CountedContribution<T>>::insert((campaign_id, investor.clone()), counted);
+		}
+	}
+
+	// The largest balance whose square is at most `n`
+	fn integer_sqrt(n: BalanceOf<T>) -> BalanceOf<T> {
+		let two = BalanceOf::<T>::sa(2);
+		if n < two {
+			return n;
+		}
+
+		let mut root = n;
+		let mut next = n / two + n % two;
+		while next < root {
+			root = next;
+			next = (root + n / root) / two;
+		}
+		root
+	}
+
+	// Draw the matches of the sponsors of a campaign for an investment, each draw is invested in the
+	// name of its sponsor so that the sponsor is refunded like any investor if the campaign fails
+	fn draw_matches(campaign_id: T::Hash, investor: &T::AccountId, invest_amount: BalanceOf<T>) {
+		for sponsor in Self::sponsors_of(&campaign_id) {
+			if sponsor == *investor {
+				continue;
+			}
+			let mut offer = match Self::sponsor_offer_of((campaign_id, sponsor.clone())) {
+				Some(offer) => offer,
+				None => continue,
+			};
+
+			let matched = invest_amount * BalanceOf::<T>::sa(offer.match_percentage as u64) / BalanceOf::<T>::sa(100);
+			let available = offer.cap - offer.drawn;
+			let draw = if matched < available { matched } else { available };
+			if draw == BalanceOf::<T>::sa(0) {
+				continue;
+			}
+
+			// A draw which can't be invested, e.g. when the campaign is full, is reserved again and skipped
+			let _ = T::Currency::unreserve(&sponsor, draw);
+			let invested = if <InvestAmount<T>>::exists((campaign_id, sponsor.clone())) {
+				Self::invest_before(sponsor.clone(), campaign_id, draw)
+			} else {
+				Self::not_invest_before(sponsor.clone(), campaign_id, draw)
+			};
+			if invested.is_err() {
+				let _ = T::Currency::reserve(&sponsor, draw);
+				continue;
+			}
+
+			offer.drawn = offer.drawn + draw;
+			<SponsorOffers<T>>::insert((campaign_id, sponsor.clone()), offer);
+			<MatchesDrawnBy<T>>::mutate((campaign_id, investor.clone()), |draws| {
+				match draws.iter_mut().find(|(drawn_from, _)| *drawn_from == sponsor) {
+					Some((_, drawn)) => *drawn = *drawn + draw,
+					None => draws.push((sponsor.clone(), draw)),
+				}
+			});
+			Self::deposit_event(RawEvent::MatchDrawn(campaign_id, sponsor, draw));
+		}
+	}
+
+	// Give the sponsors back the matches an investor drew, in proportion to the withdrawn part of
+	// the investment, so that investing and withdrawing again can't move a cap into the campaign
+	fn return_matches(campaign_id: T::Hash, investor: &T::AccountId, withdraw_amount: BalanceOf<T>, invested: BalanceOf<T>) {
+		let draws = Self::matches_drawn_by((campaign_id, investor.clone()));
+		if draws.is_empty() {
+			return;
+		}
+
+		let mut remaining_draws = Vec::new();
+		for (sponsor, drawn) in draws {
+			let returned = if withdraw_amount == invested {
+				drawn
+			} else {
+				drawn * withdraw_amount / invested
+			};
+			let sponsor_investment = Self::invest_amount_of((campaign_id, sponsor.clone()));
+			if returned == BalanceOf::<T>::sa(0) || returned > sponsor_investment {
+				remaining_draws.push((sponsor, drawn));
+				continue;
+			}
+
+			// The match goes back into the reserved cap of the sponsor
+			if Self::pay_out_of_escrow(&sponsor, returned).is_err() {
+				remaining_draws.push((sponsor, drawn));
+				continue;
+			}
+			let _ = T::Currency::reserve(&sponsor, returned);
+			<EscrowBalance<T>>::mutate(&campaign_id, |balance| *balance = *balance - returned);
+			<CampaignSupportedAmount<T>>::mutate(&campaign_id, |amount| *amount = *amount - returned);
+			if returned == sponsor_investment {
+				let _ = Self::remove_investor(sponsor.clone(), campaign_id);
+			} else {
+				<InvestAmount<T>>::insert((campaign_id, sponsor.clone()), sponsor_investment - returned);
+			}
+			<SponsorOffers<T>>::mutate((campaign_id, sponsor.clone()), |offer| {
+				if let Some(offer) = offer {
+					offer.drawn = offer.drawn - returned;
+				}
+			});
+
+			if drawn > returned {
+				remaining_draws.push((sponsor.clone(), drawn - returned));
+			}
+			Self::deposit_event(RawEvent::MatchReturned(campaign_id, sponsor, returned));
+		}
+
+		if remaining_draws.is_empty() {
+			<MatchesDrawnBy<T>>::remove((campaign_id, investor.clone()));
+		} else {
+			<MatchesDrawnBy<T>>::insert((campaign_id, investor.clone()), remaining_draws);
+		}
+	}
+
+	// Give the sponsors of a campaign which stops raising money back the unused part of their caps
+	fn release_sponsor_offers(campaign_id: T::Hash) {
+		for sponsor in Self::sponsors_of(&campaign_id) {
+			if let Some(offer) = Self::sponsor_offer_of((campaign_id, sponsor.clone())) {
+				let unused = offer.cap - offer.drawn;
+				let _ = T::Currency::unreserve(&sponsor, unused);
+				Self::deposit_event(RawEvent::MatchReleased(campaign_id, sponsor.clone(), unused));
+			}
+			<SponsorOffers<T>>::remove((campaign_id, sponsor));
+		}
+		<CampaignSponsors<T>>::remove(&campaign_id);
+	}
+
+	// Stop a campaign which is still raising money, its investors can claim back their investments
+	fn cancel(campaign_id: T::Hash) {
+		// Make the status cancelled
+		let expiry = Self::campaign(&campaign_id).campaign_expiry;
+		Self::set_campaign_status(campaign_id, CampaignStatus::Cancelled);
+
+		// The campaign must not be finalized at its expiry anymore
+		<CampaignsByBlockNumber<T>>::mutate(expiry, |campaigns| campaigns.retain(|c| *c != campaign_id));
+		Self::release_sponsor_offers(campaign_id);
+
+		// every investor can claim back the whole investment
+		Self::start_refunds(campaign_id);
+
+		let amount_of_investment = Self::total_amount_of_campaign(&campaign_id);
+		Self::deposit_event(RawEvent::CampaignCancelled(campaign_id, amount_of_investment, <system::Module<T>>::block_number()));
+	}
+
+	fn release_creation_deposit(campaign_id: T::Hash) {
+		let manager = Self::campaign(&campaign_id).campaign_manager;
+		let _ = T::Currency::unreserve(&manager, Self::creation_deposit_of(&campaign_id));
+		<CreationDepositOf<T>>::remove(&campaign_id);
+	}
+
+	// Pay the platform fee of a succeeded campaign out of its escrowed money
+	fn charge_platform_fee(campaign_id: T::Hash) -> BalanceOf<T> {
+		let escrowed = Self::escrow_balance(&campaign_id);
+		let mut fee = Self::platform_fee() * Self::total_amount_of_campaign(&campaign_id);
+		if fee > escrowed {
+			fee = escrowed;
+		}
+		if fee > BalanceOf::<T>::sa(0) {
+			let _fee_transfer = Self::transfer_pledge(campaign_id, &T::escrow_account(), &Self::treasury_account(), fee);
+			if _fee_transfer.is_err() {
+				fee = BalanceOf::<T>::sa(0);
+			}
+		}
+
+		<PlatformFeeOf<T>>::insert(&campaign_id, fee);
+		<EscrowBalance<T>>::insert(&campaign_id, escrowed - fee);
+		fee
+	}
+
+	// Let every investor claim back his/her share of the escrowed money, and queue
+	// the campaign so that the investors who don't claim are refunded automatically
+	fn start_refunds(campaign_id: T::Hash) {
+		<RefundPool<T>>::insert(&campaign_id, Self::escrow_balance(&campaign_id));
+		<SettlementQueue<T>>::mutate(|queue| queue.push(campaign_id));
+	}
+
+	// Transfer the share of the refund pool of an investor back from the escrow account
+	fn settle_refund(campaign_id: T::Hash, investor: T::AccountId) -> Result {
+		let refund = Self::refund_of(campaign_id, &investor);
+		if refund > BalanceOf::<T>::sa(0) {
+			Self::transfer_pledge(campaign_id, &T::escrow_account(), &investor, refund)?;
+		}
+		<Settled<T>>::insert((campaign_id, investor.clone()), true);
+		<SettledCount<T>>::mutate(&campaign_id, |count| *count += 1);
+		<EscrowBalance<T>>::mutate(&campaign_id, |balance| *balance = *balance - refund);
+
+		Self::deposit_event(RawEvent::RefundClaimed(campaign_id, investor, refund));
+		Ok(())
+	}
+
+	// Whether the investors of a campaign get backer shares, true once it succeeded and none of its money
+	// can be refunded anymore, which is after the last milestone is released for a campaign with milestones
+	fn issues_shares(campaign_id: T::Hash) -> bool {
+		let status = Self::campaign_status(&campaign_id);
+		(status == CampaignStatus::Succeeded || status == CampaignStatus::PayoutPending)
+			&& Self::next_milestone(&campaign_id) as usize >= Self::milestones_of(&campaign_id).len()
+	}
+
+	// Issue backer shares of a campaign to an investor, one share for every unit of the investment
+	fn issue_shares(campaign_id: T::Hash, investor: T::AccountId) -> Result {
+		let invest_balance = Self::invest_amount_of((campaign_id, investor.clone()));
+		if invest_balance > BalanceOf::<T>::sa(0) {
+			<shares::Module<T>>::issue(campaign_id, &investor, invest_balance)?;
+		}
+		<SharesIssuedTo<T>>::insert((campaign_id, investor), true);
+		Ok(())
+	}
+
+	// Refund, or issue backer shares to, at most `budget` investors of the queued campaigns,
+	// oldest campaign first. An investor whose settlement fails is skipped and can still claim it later.
+	fn process_settlement_queue(mut budget: u64) {
+		while budget > 0 {
+			let campaign_id = match Self::settlement_queue().first() {
+				Some(campaign_id) => *campaign_id,
+				None => break,
+			};
+			// Visiting a campaign costs like settling an investor, even if it has no investors left to settle
+			budget -= 1;
+			let investors = Self::invest_accounts(&campaign_id);
+			let investor_count = investors.len() as u64;
+			let mut cursor = Self::settlement_cursor(&campaign_id);
+			let issues_shares = Self::issues_shares(campaign_id);
+
+			while cursor < investor_count && budget > 0 {
+				let investor = investors[cursor as usize].clone();
+				if issues_shares {
+					if !Self::has_shares((campaign_id, investor.clone())) {
+						let _ = Self::issue_shares(campaign_id, investor);
+					}
+				} else if !Self::is_settled((campaign_id, investor.clone())) {
+					let _ = Self::settle_refund(campaign_id, investor);
+				}
+				cursor += 1;
+				budget -= 1;
+			}
+
+			if cursor < investor_count {
+				<SettlementCursor<T>>::insert(&campaign_id, cursor);
+				Self::deposit_event(RawEvent::SettlementProgress(campaign_id, cursor, investor_count));
+			} else {
+				<SettlementCursor<T>>::remove(&campaign_id);
+				<SettlementQueue<T>>::mutate(|queue| { queue.remove(0); });
+				Self::deposit_event(RawEvent::SettlementCompleted(campaign_id));
+			}
+		}
+	}
+
+	// The share of the refund pool of a campaign which belongs to an investor
+	fn refund_of(campaign_id: T::Hash, investor: &T::AccountId) -> BalanceOf<T> {
+		let amount_of_investment = Self::total_amount_of_campaign(&campaign_id);
+		if amount_of_investment == BalanceOf::<T>::sa(0) {
+			return BalanceOf::<T>::sa(0);
+		}
+		let invest_balance = Self::invest_amount_of((campaign_id, investor.clone()));
+		Self::refund_pool(&campaign_id) * invest_balance / amount_of_investment
+	}
+
+	// keep the status of the campaign struct and the status map in sync
+	fn set_campaign_status(campaign_id: T::Hash, status: CampaignStatus) {
+		<Campaigns<T>>::mutate(&campaign_id, |campaign| campaign.campaign_status = status);
+		<StatusOfCampaign<T>>::insert(&campaign_id, status);
+		<StatusChangedAt<T>>::insert(&campaign_id, <system::Module<T>>::block_number());
+	}
+
+	// A campaign is settled once nobody is owed any of its money anymore
+	fn ensure_settled(campaign_id: T::Hash) -> Result {
+		match Self::campaign(&campaign_id).campaign_status {
+			CampaignStatus::Succeeded => {
+				ensure!(Self::escrow_balance(&campaign_id) == BalanceOf::<T>::sa(0), "The campaign still has money to release");
+				ensure!(!Self::settlement_queue().contains(&campaign_id), "The shares of the campaign are still being issued");
+			},
+			CampaignStatus::Failed | CampaignStatus::Cancelled | CampaignStatus::Refunded => {
+				ensure!(!Self::settlement_queue().contains(&campaign_id), "The campaign is still being refunded");
+				ensure!(
+					Self::settled_count(&campaign_id) == Self::invest_accounts_count(&campaign_id),
+					"An investor of the campaign has not been refunded yet"
+				);
+			},
+			_ => return Err("The campaign is not settled"),
+		}
+		Ok(())
+	}
+
+	// Remove the invest related fields of at most `INVESTORS_REAPED_PER_CALL` investors of a campaign,
+	// true once every investor has been reaped
+	fn reap_investors(campaign_id: T::Hash) -> bool {
+		let investors = Self::invest_accounts(&campaign_id);
+		let investor_count = investors.len() as u64;
+		let milestone_count = Self::milestones_of(&campaign_id).len() as u32;
+		let mut cursor = Self::reap_cursor(&campaign_id);
+
+		let mut budget = T::INVESTORS_REAPED_PER_CALL;
+		while cursor < investor_count && budget > 0 {
+			let investor = investors[cursor as usize].clone();
+			let _ = Self::remove_invested_campaign(investor.clone(), campaign_id);
+			for milestone_index in 0..milestone_count {
+				<MilestoneVotes<T>>::remove((campaign_id, milestone_index, investor.clone()));
+			}
+			<ClaimedRewardTier<T>>::remove((campaign_id, investor.clone()));
+			<Settled<T>>::remove((campaign_id, investor.clone()));
+			<SharesIssuedTo<T>>::remove((campaign_id, investor.clone()));
+			<MatchesDrawnBy<T>>::remove((campaign_id, investor.clone()));
+			<CountedContribution<T>>::remove((campaign_id, investor.clone()));
+			<InvestAmount<T>>::remove((campaign_id, investor));
+			cursor += 1;
+			budget -= 1;
+		}
+
+		if cursor < investor_count {
+			<ReapCursor<T>>::insert(&campaign_id, cursor);
+			Self::deposit_event(RawEvent::ReapProgress(campaign_id, cursor, investor_count));
+			false
+		} else {
+			<ReapCursor<T>>::remove(&campaign_id);
+			true
+		}
+	}
+
+	// Remove everything stored about a campaign whose investors have been reaped and return its storage deposit
+	fn reap(campaign_id: T::Hash) -> Result {
+		let campaign = Self::campaign(&campaign_id);
+		let manager = campaign.campaign_manager.clone();
+		let milestone_count = Self::milestones_of(&campaign_id).len() as u32;
+		let reward_tier_count = Self::reward_tiers_of(&campaign_id).len() as u32;
+
+		// swap and pop the campaign of all the campaigns
+		let campaign_count = Self::campaign_count();
+		let new_campaign_count = campaign_count
+			.checked_sub(1)
+			.ok_or("Underflow removing a Campaign")?;
+		let campaign_index = <AllCampaignIndex<T>>::get(&campaign_id);
+		if campaign_index != new_campaign_count {
+			let last_campaign_id = <AllCampaignArray<T>>::get(new_campaign_count);
+			<AllCampaignArray<T>>::insert(campaign_index, last_campaign_id.clone());
+			<AllCampaignIndex<T>>::insert(last_campaign_id, campaign_index);
+		}
+		<AllCampaignArray<T>>::remove(new_campaign_count);
+		<AllCampaignIndex<T>>::remove(&campaign_id);
+		<AllCampaignCount<T>>::put(new_campaign_count);
+
+		Self::remove_owned_campaign(manager.clone(), campaign_id)?;
+
+		<InvestAccounts<T>>::remove(&campaign_id);
+		<InvestAccountsCount<T>>::remove(&campaign_id);
+		<SettledCount<T>>::remove(&campaign_id);
+
+		for milestone_index in 0..milestone_count {
+			<MilestoneTally<T>>::remove((campaign_id, milestone_index));
+		}
+		<Milestones<T>>::remove(&campaign_id);
+		<NextMilestone<T>>::remove(&campaign_id);
+		<MilestoneSubmission<T>>::remove(&campaign_id);
+
+		for tier_index in 0..reward_tier_count {
+			<RewardTierClaimCount<T>>::remove((campaign_id, tier_index));
+		}
+		<RewardTiers<T>>::remove(&campaign_id);
+		<StretchGoals<T>>::remove(&campaign_id);
+		<StretchGoalsReached<T>>::remove(&campaign_id);
+		<Metadata<T>>::remove(&campaign_id);
+		<FundingModeOf<T>>::remove(&campaign_id);
+		<EarlyCloseAllowed<T>>::remove(&campaign_id);
+		<CampaignAsset<T>>::remove(&campaign_id);
+		<VestingSchedules<T>>::remove(&campaign_id);
+		<VestingTotal<T>>::remove(&campaign_id);
+		<MatchedAmount<T>>::remove(&campaign_id);
+		<ContributionSums<T>>::remove(&campaign_id);
+
+		// The rounding dust of the refunds goes to the treasury
+		let dust = Self::escrow_balance(&campaign_id);
+		if dust > BalanceOf::<T>::sa(0) {
+			let _ = Self::transfer_pledge(campaign_id, &T::escrow_account(), &Self::treasury_account(), dust);
+		}
+		<EscrowBalance<T>>::remove(&campaign_id);
+		<RefundPool<T>>::remove(&campaign_id);
+		<PlatformFeeOf<T>>::remove(&campaign_id);
+		<CampaignSupportedAmount<T>>::remove(&campaign_id);
+		<SettlementCursor<T>>::remove(&campaign_id);
+		<CampaignsByBlockNumber<T>>::mutate(campaign.campaign_expiry, |campaigns| campaigns.retain(|c| *c != campaign_id));
+
+		<StatusOfCampaign<T>>::remove(&campaign_id);
+		<StatusChangedAt<T>>::remove(&campaign_id);
+		<CampaignOwner<T>>::remove(&campaign_id);
+		<PendingCampaignOwner<T>>::remove(&campaign_id);
+		Self::clear_manager_approvals(campaign_id);
+		<CoManagers<T>>::remove(&campaign_id);
+		<ManagerThreshold<T>>::remove(&campaign_id);
+		<Campaigns<T>>::remove(&campaign_id);
+
+		let _ = T::Currency::unreserve(&manager, Self::campaign_deposit(&campaign_id));
+		<CampaignDeposit<T>>::remove(&campaign_id);
+		// The creation deposit of a cancelled campaign which nobody released yet
+		let _ = T::Currency::unreserve(&manager, Self::creation_deposit_of(&campaign_id));
+		<CreationDepositOf<T>>::remove(&campaign_id);
+
+		Ok(())
+	}
+
+	// append a campaign to the campaigns owned by an account
+	fn add_owned_campaign(owner: T::AccountId, campaign_id: T::Hash) -> Result {
+		let owned_campaign_count = Self::owned_campaign_count(&owner);
+		let new_owned_campaign_count = owned_campaign_count
+			.checked_add(1)
+			.ok_or("Overflow adding a new Campaign")?;
+
+		<OwnedCampaignArray<T>>::insert(
+			(owner.clone(), owned_campaign_count.clone()),
+			campaign_id.clone(),
+		);
+		<OwnedCampaignCount<T>>::insert(&owner, new_owned_campaign_count);
+		<OwnedCampaignIndex<T>>::insert((owner, campaign_id), owned_campaign_count);
+
+		Ok(())
+	}
+
+	// swap and pop a campaign of the campaigns owned by an account
+	fn remove_owned_campaign(owner: T::AccountId, campaign_id: T::Hash) -> Result {
+		let owned_campaign_count = Self::owned_campaign_count(&owner);
+		let new_owned_campaign_count = owned_campaign_count
+			.checked_sub(1)
+			.ok_or("Underflow removing an owned Campaign")?;
+
+		let owned_campaign_index = <OwnedCampaignIndex<T>>::get((owner.clone(), campaign_id.clone()));
+		if owned_campaign_index != new_owned_campaign_count {
+			let last_campaign_id = <OwnedCampaignArray<T>>::get((owner.clone(), new_owned_campaign_count));
+			<OwnedCampaignArray<T>>::insert((owner.clone(), owned_campaign_index), last_campaign_id.clone());
+			<OwnedCampaignIndex<T>>::insert((owner.clone(), last_campaign_id), owned_campaign_index);
+		}
+		<OwnedCampaignArray<T>>::remove((owner.clone(), new_owned_campaign_count));
+		<OwnedCampaignIndex<T>>::remove((owner.clone(), campaign_id));
+		<OwnedCampaignCount<T>>::insert(&owner, new_owned_campaign_count);
+
+		Ok(())
+	}
+
+	// swap and pop a campaign of the campaigns an account invested in
+	fn remove_invested_campaign(investor: T::AccountId, campaign_id: T::Hash) -> Result {
+		let invested_campaign_count = Self::invested_campaign_count(&investor);
+		let new_invested_campaign_count = invested_campaign_count
+			.checked_sub(1)
+			.ok_or("Underflow removing an invested Campaign")?;
+
+		let invested_campaign_index = <InvestedCampaignsIndex<T>>::get((investor.clone(), campaign_id.clone()));
+		if invested_campaign_index != new_invested_campaign_count {
+			let last_campaign_id = <InvestedCampaignsArray<T>>::get((investor.clone(), new_invested_campaign_count));
+			<InvestedCampaignsArray<T>>::insert((investor.clone(), invested_campaign_index), last_campaign_id.clone());
+			<InvestedCampaignsIndex<T>>::insert((investor.clone(), last_campaign_id), invested_campaign_index);
+		}
+		<InvestedCampaignsArray<T>>::remove((investor.clone(), new_invested_campaign_count));
+		<InvestedCampaignsIndex<T>>::remove((investor.clone(), campaign_id));
+		<InvestedCampaignsCount<T>>::insert(&investor, new_invested_campaign_count);
+
+		Ok(())
+	}
+
+	// Campaigns created before `CampaignStatus` stored their status as a little endian `u64`
+	// of 0, 1, 2 or 3. The first byte of it decodes to the matching variant and the trailing
+	// bytes are never read, so an old campaign can be decoded and written back as it is.
+	fn migrate_campaign_status(campaign_id: T::Hash) {
+		let campaign = Self::campaign(&campaign_id);
+		<StatusOfCampaign<T>>::insert(&campaign_id, campaign.campaign_status);
+		<Campaigns<T>>::insert(&campaign_id, campaign);
+	}
+
+	// Migrate the campaigns created before the escrow rework. Their status is migrated to `CampaignStatus`
+	// first. The pledges of a campaign raising money were reserved on the investors, and the payout of a
+	// succeeded campaign was reserved on its manager. Move at most `budget` pledges into the escrow account
+	// and track them in `EscrowBalance`, release the reserved payouts, and return what is left of the budget.
+	// Visiting a campaign costs like moving a pledge, like in the settlement queue.
+	fn migrate_campaigns(mut budget: u64) -> u64 {
+		let end = match Self::migration_end() {
+			Some(end) => end,
+			// The escrow account has to hold its existential deposit before any pledge moves into it
+			None => {
+				if Self::endow_escrow_account().is_err() {
+					return budget;
+				}
+				let end = Self::campaign_count();
+				<MigrationEnd<T>>::put(end);
+				end
+			},
+		};
+
+		let (mut campaign_index, mut investor_index) = Self::migration_cursor();
+		while campaign_index < end && budget > 0 {
+			budget -= 1;
+			let campaign_id = Self::campaign_by_index(campaign_index);
+			if investor_index == 0 {
+				Self::migrate_campaign_status(campaign_id);
+			}
+			let campaign = Self::campaign(&campaign_id);
+
+			match campaign.campaign_status {
+				CampaignStatus::Funding => {
+					let mut investors = Self::invest_accounts(&campaign_id);
+					while (investor_index as usize) < investors.len() && budget > 0 {
+						budget -= 1;
+						let investor = investors[investor_index as usize].clone();
+						if Self::migrate_pledge(campaign_id, investor) {
+							// The last investor was swapped into the place of the removed one
+							investors.swap_remove(investor_index as usize);
+						} else {
+							investor_index += 1;
+						}
+					}
+					if (investor_index as usize) < investors.len() {
+						break;
+					}
+				},
+				CampaignStatus::Succeeded => {
+					let _ = T::Currency::unreserve(&campaign.campaign_manager, Self::total_amount_of_campaign(&campaign_id));
+				},
+				_ => {},
+			}
+			campaign_index += 1;
+			investor_index = 0;
+		}
+
+		if campaign_index < end {
+			<MigrationCursor<T>>::put((campaign_index, investor_index));
+		} else {
+			<MigrationCursor<T>>::kill();
+			<MigrationEnd<T>>::kill();
+			<CampaignsMigrated<T>>::put(true);
+			Self::deposit_event(RawEvent::MigrationCompleted(end));
+		}
+		budget
+	}
+
+	// Move a pledge reserved on an investor into the escrow account. A pledge which can't be moved is given
+	// back to the investor instead, so that the escrow balance of the campaign stays backed. True if the
+	// investor was removed from the campaign.
+	fn migrate_pledge(campaign_id: T::Hash, investor: T::AccountId) -> bool {
+		let invest_balance = Self::invest_amount_of((campaign_id, investor.clone()));
+		let _ = T::Currency::unreserve(&investor, invest_balance);
+		if T::Currency::transfer(&investor, &T::escrow_account(), invest_balance).is_ok() {
+			<EscrowBalance<T>>::mutate(&campaign_id, |balance| *balance = *balance + invest_balance);
+			Self::recount_contribution(campaign_id, &investor);
+			return false;
+		}
+
+		Self::set_reward_tier(campaign_id, investor.clone(), None);
+		if Self::remove_investor(investor, campaign_id).is_err() {
+			return false;
+		}
+		<CampaignSupportedAmount<T>>::mutate(&campaign_id, |amount| *amount = *amount - invest_balance);
+		true
+	}
+
+	// Top the escrow account up to its existential deposit out of the treasury. It is endowed before it holds
+	// any tracked money, so the money of the campaigns never counts towards the deposit.
+	fn endow_escrow_account() -> Result {
+		let escrow_account = T::escrow_account();
+		let existential_deposit = T::Currency::minimum_balance();
+		let free_balance = T::Currency::free_balance(&escrow_account);
+		if free_balance >= existential_deposit {
+			return Ok(());
+		}
+
+		let shortfall = existential_deposit - free_balance;
+		let endowment = T::Currency::transfer(&Self::treasury_account(), &escrow_account, shortfall);
+		if endowment.is_err() {
+			Self::deposit_event(RawEvent::EscrowAccountUnendowed(shortfall));
+		}
+		endowment
+	}
+
+	// The campaigns created before the escrow rework can't be used until the migration reached them
+	fn ensure_migrated(campaign_id: T::Hash) -> Result {
+		if Self::campaigns_migrated() {
+			return Ok(());
+		}
+		let end = Self::migration_end().ok_or("The campaigns are still being migrated")?;
+		let (cursor, _) = Self::migration_cursor();
+		let index = <AllCampaignIndex<T>>::get(&campaign_id);
+		ensure!(index < cursor || index >= end, "The campaign is still being migrated");
+		Ok(())
+	}
+
+	// The investor withdraws the whole investment, remove all of his/her invest related fields
+	fn remove_investor(sender: T::AccountId, campaign_id: T::Hash) -> Result {
+		let investor_count = <InvestAccountsCount<T>>::get(&campaign_id);
+		let new_investor_count = investor_count
+			.checked_sub(1)
+			.ok_or("Underflow subtracting the total number of investors of a campaign")?;
+
+		Self::remove_invested_campaign(sender.clone(), campaign_id.clone())?;
+
+		// swap and pop the investor of the campaign
+		<InvestAccounts<T>>::mutate(&campaign_id, |accounts| {
+			if let Some(position) = accounts.iter().position(|a| *a == sender) {
+				accounts.swap_remove(position);
+			}
+		});
+		<InvestAccountsCount<T>>::insert(campaign_id.clone(), new_investor_count);
+
+		<InvestAmount<T>>::remove((campaign_id, sender));
 
 		Ok(())
 	}
@@ -58,6 +58,12 @@ pub type Nonce = u64;
 /// Used for the module template in `./template.rs`
 mod kickstart;
 
+/// Fungible assets the campaigns can raise money in
+mod assets;
+
+/// Backer shares issued to the investors of succeeded campaigns
+mod shares;
+
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
 /// the specifics of the runtime. They can then be made to be agnostic over specific formats
 /// of data like extrinsics, allowing for them to continue syncing the network through upgrades
@@ -95,8 +101,8 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("kickstarter"),
 	impl_name: create_runtime_str!("kickstarter"),
 	authoring_version: 3,
-	spec_version: 4,
-	impl_version: 4,
+	spec_version: 5,
+	impl_version: 5,
 	apis: RUNTIME_API_VERSIONS,
 };
 
@@ -187,9 +193,49 @@ impl sudo::Trait for Runtime {
 	type Proposal = Call;
 }
 
+impl assets::Trait for Runtime {
+	/// The uniquitous event type.
+	type Event = Event;
+	/// The same balance type as the native currency, so that campaigns can raise money in either.
+	type Balance = u128;
+}
+
+impl shares::Trait for Runtime {
+	/// The uniquitous event type.
+	type Event = Event;
+}
+
 /// Used for the module template in `./template.rs`
 impl kickstart::Trait for Runtime {
 	type Event = Event;
+	type Currency = Balances;
+
+	const MAX_NAME_LENGTH: usize = 128;
+	const MAX_METADATA_FIELD_LENGTH: usize = 256;
+	const MAX_INVESTORS_PER_CAMPAIGN: u64 = 10_000;
+	const MAX_MILESTONES_PER_CAMPAIGN: usize = 10;
+	const MAX_REWARD_TIERS_PER_CAMPAIGN: usize = 10;
+	const MAX_STRETCH_GOALS_PER_CAMPAIGN: usize = 10;
+	const MAX_CO_MANAGERS_PER_CAMPAIGN: usize = 10;
+	const MAX_CAMPAIGNS_PER_ROUND: usize = 50;
+	const MAX_SPONSORS_PER_CAMPAIGN: usize = 10;
+	/// About a day with 10 second blocks.
+	const MILESTONE_REVIEW_PERIOD: BlockNumber = 8640;
+	const MILESTONE_REJECTION_THRESHOLD: u64 = 50;
+	const SETTLEMENTS_PER_BLOCK: u64 = 100;
+	/// About a week with 10 second blocks.
+	const REAP_GRACE_PERIOD: BlockNumber = 60480;
+	const INVESTORS_REAPED_PER_CALL: u64 = 100;
+
+	fn escrow_account() -> AccountId {
+		kickstart_escrow_account()
+	}
+}
+
+/// The account holding the invested money of every campaign, nobody knows its private key.
+/// It is endowed with the existential deposit at genesis so that it is never reaped.
+pub fn kickstart_escrow_account() -> AccountId {
+	AccountId::from_raw(*b"modlkickstart/escrow\0\0\0\0\0\0\0\0\0\0\0\0")
 }
 
 construct_runtime!(
@@ -206,7 +252,9 @@ construct_runtime!(
 		Balances: balances,
 		Sudo: sudo,
 		// Used for the module template in `./template.rs`
-		KickstartModule: kickstart::{Module, Call, Storage, Event<T>},
+		KickstartModule: kickstart::{Module, Call, Storage, Event<T>, Config<T>},
+		Assets: assets::{Module, Call, Storage, Event<T>},
+		Shares: shares::{Module, Call, Storage, Event<T>},
 	}
 );
 
@@ -0,0 +1,80 @@
+use runtime_primitives::traits::{As, CheckedAdd, CheckedSub};
+use support::{decl_event, decl_module, decl_storage, dispatch::Result, ensure, StorageMap};
+use system::ensure_signed;
+
+use crate::assets;
+
+/// Fungible shares of the backers of a campaign, identified by the id of the campaign.
+/// They share the balance type of the assets.
+pub trait Trait: assets::Trait {
+	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+}
+
+decl_event!(
+	pub enum Event<T>
+	where
+		<T as system::Trait>::AccountId,
+		<T as system::Trait>::Hash,
+		<T as assets::Trait>::Balance
+	{
+		// share id, owner, amount
+		SharesIssued(Hash, AccountId, Balance),
+		// share id, from, to, amount
+		SharesTransferred(Hash, AccountId, AccountId, Balance),
+	}
+);
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Shares {
+		// The shares of an account in a campaign
+		Balances get(balance_of): map (T::Hash, T::AccountId) => T::Balance;
+		// The total amount of shares issued for a campaign
+		TotalSupply get(total_supply): map T::Hash => T::Balance;
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event<T>() = default;
+
+		/// transfer shares of a campaign to another account
+		fn transfer(origin, share_id: T::Hash, target: T::AccountId, amount: T::Balance) -> Result {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(amount > T::Balance::sa(0), "The amount has to be greater than zero");
+			let sender_balance = Self::balance_of((share_id, sender.clone()));
+			let new_sender_balance = sender_balance
+				.checked_sub(&amount)
+				.ok_or("You don't have enough shares")?;
+
+			if sender != target {
+				let new_target_balance = Self::balance_of((share_id, target.clone()))
+					.checked_add(&amount)
+					.ok_or("Overflow adding to the shares of the target")?;
+				<Balances<T>>::insert((share_id, sender.clone()), new_sender_balance);
+				<Balances<T>>::insert((share_id, target.clone()), new_target_balance);
+			}
+
+			Self::deposit_event(RawEvent::SharesTransferred(share_id, sender, target, amount));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Issue new shares of a campaign to an account.
+	pub fn issue(share_id: T::Hash, owner: &T::AccountId, amount: T::Balance) -> Result {
+		let new_total_supply = Self::total_supply(&share_id)
+			.checked_add(&amount)
+			.ok_or("Overflow adding to the total supply of the shares")?;
+		let new_balance = Self::balance_of((share_id, owner.clone()))
+			.checked_add(&amount)
+			.ok_or("Overflow adding to the shares of the owner")?;
+
+		<TotalSupply<T>>::insert(&share_id, new_total_supply);
+		<Balances<T>>::insert((share_id, owner.clone()), new_balance);
+
+		Self::deposit_event(RawEvent::SharesIssued(share_id, owner.clone(), amount));
+		Ok(())
+	}
+}
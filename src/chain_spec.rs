@@ -1,7 +1,7 @@
 use primitives::{ed25519, sr25519, Pair};
 use kickstarter_runtime::{
 	AccountId, GenesisConfig, ConsensusConfig, TimestampConfig, BalancesConfig,
-	SudoConfig, IndicesConfig,
+	SudoConfig, IndicesConfig, KickstartModuleConfig, Perbill, kickstart_escrow_account,
 };
 use substrate_service;
 
@@ -91,6 +91,7 @@ impl Alternative {
 }
 
 fn testnet_genesis(initial_authorities: Vec<AuthorityId>, endowed_accounts: Vec<AccountId>, root_key: AccountId) -> GenesisConfig {
+	let existential_deposit = 500;
 	GenesisConfig {
 		consensus: Some(ConsensusConfig {
 			code: include_bytes!("../runtime/wasm/target/wasm32-unknown-unknown/release/kickstarter_runtime_wasm.compact.wasm").to_vec(),
@@ -106,14 +107,27 @@ fn testnet_genesis(initial_authorities: Vec<AuthorityId>, endowed_accounts: Vec<
 		balances: Some(BalancesConfig {
 			transaction_base_fee: 1,
 			transaction_byte_fee: 0,
-			existential_deposit: 500,
+			existential_deposit,
 			transfer_fee: 0,
 			creation_fee: 0,
-			balances: endowed_accounts.iter().cloned().map(|k|(k, 1 << 60)).collect(),
+			// The escrow account keeps the existential deposit so that it is never reaped
+			balances: endowed_accounts.iter().cloned().map(|k|(k, 1 << 60))
+				.chain(Some((kickstart_escrow_account(), existential_deposit)))
+				.collect(),
 			vesting: vec![],
 		}),
 		sudo: Some(SudoConfig {
-			key: root_key,
+			key: root_key.clone(),
+		}),
+		kickstart: Some(KickstartModuleConfig {
+			campaign_period_limit: 864000,
+			minimum_target: 10_000,
+			minimum_investment: 500,
+			platform_fee: Perbill::from_percent(1),
+			treasury_account: root_key,
+			storage_deposit: 1_000,
+			creation_deposit: 10_000,
+			creation_deposit_hold: 14_400,
 		}),
 	}
 }